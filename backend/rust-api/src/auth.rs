@@ -1,39 +1,77 @@
 use crate::database::DatabaseService;
 use anyhow::Result;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm as Argon2Algorithm, Argon2, Params, Version};
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub user_id: i32,
     pub email: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
     pub exp: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Claims {
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct LoginResponse {
     pub success: bool,
     pub token: String,
+    pub refresh_token: String,
     pub user: UserInfo,
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct RefreshResponse {
     pub success: bool,
     pub token: String,
+    pub refresh_token: String,
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LogoutRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SessionInfo {
+    pub id: i32,
+    pub device: Option<String>,
+    pub created_at: String,
+    pub expires_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SessionsResponse {
+    pub success: bool,
+    pub sessions: Vec<SessionInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct UserInfo {
     pub id: i32,
     pub email: String,
@@ -47,9 +85,42 @@ pub struct UserInfo {
     pub github: Option<String>,
 }
 
+/// Argon2id cost parameters, tunable via env so memory/iteration cost can be raised
+/// as hardware improves without a code change.
+struct Argon2Config {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl Argon2Config {
+    fn from_env() -> Self {
+        Self {
+            memory_kib: env_u32("ARGON2_MEMORY_KIB", 19_456), // ~19 MiB, OWASP default
+            iterations: env_u32("ARGON2_ITERATIONS", 2),
+            parallelism: env_u32("ARGON2_PARALLELISM", 1),
+        }
+    }
+}
+
+fn env_u32(key: &str, default: u32) -> u32 {
+    std::env::var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+fn random_hex_token() -> String {
+    let mut buf = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut buf);
+    hex::encode(buf)
+}
+
+fn hash_refresh_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
 pub struct AuthService {
     db: Arc<DatabaseService>,
     jwt_secret: String,
+    argon2_config: Argon2Config,
 }
 
 impl AuthService {
@@ -57,25 +128,107 @@ impl AuthService {
         Self {
             db,
             jwt_secret: std::env::var("JWT_SECRET_KEY").unwrap_or_else(|_| "your-secret-key-change-in-production-2024".to_string()),
+            argon2_config: Argon2Config::from_env(),
         }
     }
 
-    pub async fn login(&self, email: &str, password: &str) -> Result<LoginResponse> {
+    pub async fn login(&self, email: &str, password: &str, device: Option<&str>) -> Result<LoginResponse> {
         // Get user from database
         let user = self.db.get_user_by_email(email).await?;
-        
-        // Verify password (in production, use proper password hashing)
+
+        // Verify password, dispatching to bcrypt or Argon2id based on the PHC prefix
         if !self.verify_password(password, &user.password_hash)? {
             return Err(anyhow::anyhow!("Invalid credentials"));
         }
 
-        // Generate JWT token
-        let token = self.generate_token(user.id, &user.email)?;
+        // Transparently migrate legacy bcrypt hashes to Argon2id now that we know
+        // the plaintext is correct, so the whole user base upgrades as people log in.
+        if user.password_hash.starts_with("$2b$") || user.password_hash.starts_with("$2a$") || user.password_hash.starts_with("$2y$") {
+            let new_hash = self.hash_password(password)?;
+            self.db.update_password_hash(user.id, &new_hash).await?;
+        }
+
+        self.issue_session(user, device).await
+    }
+
+    /// Rotates an opaque refresh token: validates it, marks it used, and issues a
+    /// fresh access/refresh pair in the same session family. If the presented
+    /// token was already used, that's a replay signal, so the whole family is
+    /// revoked instead (the session is dead either way).
+    pub async fn refresh_token(&self, refresh_token: &str) -> Result<RefreshResponse> {
+        let hash = hash_refresh_token(refresh_token);
+        let session = self.db.get_session_by_hash(&hash).await?;
+
+        if session.revoked {
+            return Err(anyhow::anyhow!("Session has been revoked"));
+        }
+        if session.used {
+            self.db.revoke_session_family(&session.family_id).await?;
+            return Err(anyhow::anyhow!("Refresh token reuse detected; session revoked"));
+        }
+        let expires_at = chrono::DateTime::parse_from_rfc3339(&session.expires_at)
+            .map_err(|e| anyhow::anyhow!("Invalid session expiry: {e}"))?;
+        if expires_at < Utc::now() {
+            return Err(anyhow::anyhow!("Refresh token has expired"));
+        }
+
+        self.db.mark_session_used(session.id).await?;
+
+        let user = self.db.get_user_by_id(session.user_id).await?;
+        let access_token = self.generate_token(user.id, &user.email).await?;
+        let new_refresh_token = self.create_refresh_token(user.id, &session.family_id, session.device.as_deref()).await?;
+
+        Ok(RefreshResponse {
+            success: true,
+            token: access_token,
+            refresh_token: new_refresh_token,
+            message: "Token refreshed successfully".to_string(),
+        })
+    }
+
+    pub async fn logout(&self, refresh_token: &str) -> Result<()> {
+        let hash = hash_refresh_token(refresh_token);
+        let session = self.db.get_session_by_hash(&hash).await?;
+        self.db.revoke_session(session.id, session.user_id).await?;
+        Ok(())
+    }
+
+    pub async fn list_sessions(&self, user_id: i32) -> Result<SessionsResponse> {
+        let sessions = self.db.list_sessions(user_id).await?;
+        Ok(SessionsResponse {
+            success: true,
+            sessions: sessions
+                .into_iter()
+                .map(|s| SessionInfo {
+                    id: s.id,
+                    device: s.device,
+                    created_at: s.created_at,
+                    expires_at: s.expires_at,
+                })
+                .collect(),
+        })
+    }
+
+    pub async fn revoke_session(&self, user_id: i32, session_id: i32) -> Result<()> {
+        self.db.revoke_session(session_id, user_id).await
+    }
+
+    /// Issues a normal access/refresh token pair for a user who authenticated via
+    /// an external flow (SSO, passkeys, ...) instead of the local password path.
+    pub async fn issue_token_for_user(&self, user_id: i32) -> Result<LoginResponse> {
+        let user = self.db.get_user_by_id(user_id).await?;
+        self.issue_session(user, None).await
+    }
+
+    async fn issue_session(&self, user: crate::database::User, device: Option<&str>) -> Result<LoginResponse> {
+        let token = self.generate_token(user.id, &user.email).await?;
+        let family_id = random_hex_token();
+        let refresh_token = self.create_refresh_token(user.id, &family_id, device).await?;
 
-        // Return response
         Ok(LoginResponse {
             success: true,
             token,
+            refresh_token,
             user: UserInfo {
                 id: user.id,
                 email: user.email,
@@ -92,19 +245,26 @@ impl AuthService {
         })
     }
 
-    pub async fn refresh_token(&self, auth_header: &str) -> Result<RefreshResponse> {
-        let token = auth_header.strip_prefix("Bearer ").unwrap_or(auth_header);
-        let user_id = self.verify_token(token).await?;
+    /// Mints a new opaque 256-bit refresh token, persists its hash as a session
+    /// row in `family_id`'s rotation chain, and returns the plaintext token.
+    async fn create_refresh_token(&self, user_id: i32, family_id: &str, device: Option<&str>) -> Result<String> {
+        let refresh_token = random_hex_token();
+        let hash = hash_refresh_token(&refresh_token);
+        let now = Utc::now();
+        let expires_at = now + Duration::days(30);
 
-        // Get user data to generate new token
-        let user = self.db.get_user_by_id(user_id).await?;
-        let new_token = self.generate_token(user_id, &user.email)?;
+        self.db
+            .create_session(
+                user_id,
+                &hash,
+                family_id,
+                device,
+                &now.to_rfc3339(),
+                &expires_at.to_rfc3339(),
+            )
+            .await?;
 
-        Ok(RefreshResponse {
-            success: true,
-            token: new_token,
-            message: "Token refreshed successfully".to_string(),
-        })
+        Ok(refresh_token)
     }
 
     pub async fn verify_token(&self, token: &str) -> Result<i32> {
@@ -114,11 +274,14 @@ impl AuthService {
         Ok(claims.user_id)
     }
 
-    fn generate_token(&self, user_id: i32, email: &str) -> Result<String> {
-        let expiration = Utc::now() + Duration::hours(24);
+    async fn generate_token(&self, user_id: i32, email: &str) -> Result<String> {
+        let roles = self.db.get_user_roles(user_id).await.unwrap_or_else(|_| vec!["user".to_string()]);
+
+        let expiration = Utc::now() + Duration::minutes(15);
         let claims = Claims {
             user_id,
             email: email.to_string(),
+            roles,
             exp: expiration.timestamp(),
         };
 
@@ -127,17 +290,116 @@ impl AuthService {
         Ok(token)
     }
 
-    fn decode_token(&self, token: &str) -> Result<Claims> {
+    pub(crate) fn decode_token(&self, token: &str) -> Result<Claims> {
         let validation = Validation::new(Algorithm::HS256);
         let token_data = decode::<Claims>(token, &DecodingKey::from_secret(self.jwt_secret.as_ref()), &validation)?;
         Ok(token_data.claims)
     }
 
+    /// Hashes a new password as an Argon2id PHC string, the default for new users
+    /// and for users migrated off bcrypt on their next successful login.
+    fn hash_password(&self, password: &str) -> Result<String> {
+        let params = Params::new(
+            self.argon2_config.memory_kib,
+            self.argon2_config.iterations,
+            self.argon2_config.parallelism,
+            None,
+        )
+        .map_err(|e| anyhow::anyhow!("Invalid Argon2 parameters: {e}"))?;
+        let argon2 = Argon2::new(Argon2Algorithm::Argon2id, Version::V0x13, params);
+        let salt = SaltString::generate(&mut rand::thread_rng());
+        let hash = argon2
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|e| anyhow::anyhow!("Failed to hash password: {e}"))?;
+        Ok(hash.to_string())
+    }
+
+    /// The PHC prefix (`$argon2id$` vs `$2b$`/`$2a$`/`$2y$`) tells us which verifier
+    /// to use, so both old bcrypt hashes and new Argon2id hashes keep working.
     fn verify_password(&self, password: &str, hash: &str) -> Result<bool> {
-        // Use bcrypt for password verification
-        match bcrypt::verify(password, hash) {
-            Ok(is_valid) => Ok(is_valid),
-            Err(_) => Ok(false), // If verification fails, password is invalid
+        if hash.starts_with("$argon2") {
+            let parsed_hash = match PasswordHash::new(hash) {
+                Ok(h) => h,
+                Err(_) => return Ok(false),
+            };
+            Ok(Argon2::default().verify_password(password.as_bytes(), &parsed_hash).is_ok())
+        } else {
+            match bcrypt::verify(password, hash) {
+                Ok(is_valid) => Ok(is_valid),
+                Err(_) => Ok(false), // If verification fails, password is invalid
+            }
+        }
+    }
+}
+
+/// Axum extractor that decodes the `Authorization: Bearer` header once and injects
+/// the full `Claims` into any handler declaring it as an argument, so handlers
+/// don't each parse the header themselves.
+pub struct AuthUser(pub Claims);
+
+#[axum::async_trait]
+impl<S> axum::extract::FromRequestParts<S> for AuthUser
+where
+    crate::AppState: axum::extract::FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = axum::http::StatusCode;
+
+    async fn from_request_parts(parts: &mut axum::http::request::Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let app_state = crate::AppState::from_ref(state);
+
+        let auth_header = parts
+            .headers
+            .get("authorization")
+            .and_then(|h| h.to_str().ok())
+            .ok_or(axum::http::StatusCode::UNAUTHORIZED)?;
+
+        let token = auth_header.strip_prefix("Bearer ").unwrap_or(auth_header);
+        let claims = app_state
+            .auth_service
+            .decode_token(token)
+            .map_err(|_| axum::http::StatusCode::UNAUTHORIZED)?;
+
+        Ok(AuthUser(claims))
+    }
+}
+
+/// Marker trait naming a role, so `RequireRole<Admin>` reads like the
+/// `RequireRole("admin")` shorthand without needing const-generic string params
+/// (not stable). Add a new zero-sized marker type per role as routes need them.
+pub trait RoleName {
+    const NAME: &'static str;
+}
+
+pub struct Admin;
+impl RoleName for Admin {
+    const NAME: &'static str = "admin";
+}
+
+pub struct Recruiter;
+impl RoleName for Recruiter {
+    const NAME: &'static str = "recruiter";
+}
+
+/// Parameterized extractor that gates a handler on a required role/scope, e.g.
+/// `RequireRole<Admin>` — rejects with `403 FORBIDDEN` when the caller's JWT
+/// doesn't carry that role. Built on top of `AuthUser` so the JWT is only decoded once.
+pub struct RequireRole<R: RoleName>(pub Claims, pub std::marker::PhantomData<R>);
+
+#[axum::async_trait]
+impl<S, R> axum::extract::FromRequestParts<S> for RequireRole<R>
+where
+    crate::AppState: axum::extract::FromRef<S>,
+    S: Send + Sync,
+    R: RoleName + Send + Sync,
+{
+    type Rejection = axum::http::StatusCode;
+
+    async fn from_request_parts(parts: &mut axum::http::request::Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthUser(claims) = AuthUser::from_request_parts(parts, state).await?;
+        if !claims.has_role(R::NAME) {
+            return Err(axum::http::StatusCode::FORBIDDEN);
         }
+        Ok(RequireRole(claims, std::marker::PhantomData))
     }
 }
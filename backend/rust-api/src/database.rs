@@ -1,8 +1,14 @@
 use anyhow::Result;
-use rusqlite::Connection;
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::collections::HashSet;
+
+/// Default pool size used by [`DatabaseService::new`]. Callers that need to
+/// tune concurrency (e.g. for load testing) can go through
+/// [`DatabaseService::with_pool_size`] instead.
+const DEFAULT_POOL_SIZE: u32 = 8;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct User {
@@ -20,7 +26,7 @@ pub struct User {
     pub data_consent: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct Internship {
     pub id: i32,
     pub title: String,
@@ -45,150 +51,810 @@ pub struct Recommendation {
     pub explanation: String,
 }
 
+/// Wraps a pooled SQLite connection. Every method hands its query off to
+/// [`tokio::task::spawn_blocking`] so a slow query only ties up one blocking
+/// thread and a borrowed connection, rather than serializing the whole
+/// service behind a single `Mutex<Connection>`.
 pub struct DatabaseService {
-    conn: Arc<Mutex<Connection>>,
+    pool: Pool<SqliteConnectionManager>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApplicationFeedback {
+    pub id: i32,
+    pub user_id: i32,
+    pub internship_id: i32,
+    pub outcome: String,
+    pub rating: Option<i32>,
+    pub comment: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Session {
+    pub id: i32,
+    pub user_id: i32,
+    pub refresh_token_hash: String,
+    pub family_id: String,
+    pub device: Option<String>,
+    pub created_at: String,
+    pub expires_at: String,
+    pub revoked: bool,
+    pub used: bool,
+}
+
+fn map_application_feedback_row(row: &rusqlite::Row) -> rusqlite::Result<ApplicationFeedback> {
+    Ok(ApplicationFeedback {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        internship_id: row.get(2)?,
+        outcome: row.get(3)?,
+        rating: row.get(4)?,
+        comment: row.get(5)?,
+        created_at: row.get(6)?,
+    })
 }
 
 impl DatabaseService {
     pub fn new(db_path: &str) -> Self {
-        let conn = Connection::open(db_path).expect("Failed to open database");
-        Self {
-            conn: Arc::new(Mutex::new(conn)),
-        }
+        Self::with_pool_size(db_path, DEFAULT_POOL_SIZE)
+    }
+
+    /// Builds the connection pool and runs startup schema migrations against
+    /// one connection checked out from it. `pool_size` is the pool's maximum
+    /// number of open connections (r2d2's `max_size`).
+    pub fn with_pool_size(db_path: &str, pool_size: u32) -> Self {
+        // WAL lets readers and writers proceed concurrently instead of the
+        // default rollback-journal mode serializing them; busy_timeout makes
+        // a writer wait out a momentary lock instead of failing the query
+        // with SQLITE_BUSY under the concurrency this pool is meant to allow.
+        let manager = SqliteConnectionManager::file(db_path).with_init(|conn| {
+            conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA busy_timeout = 5000;")
+        });
+        let pool = Pool::builder()
+            .max_size(pool_size)
+            .build(manager)
+            .expect("Failed to build SQLite connection pool");
+
+        let conn = pool.get().expect("Failed to get connection from pool");
+        Self::run_migrations(&conn);
+
+        Self { pool }
+    }
+
+    fn run_migrations(conn: &Connection) {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS sessions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                user_id INTEGER NOT NULL,
+                refresh_token_hash TEXT NOT NULL UNIQUE,
+                family_id TEXT NOT NULL,
+                device TEXT,
+                created_at TEXT NOT NULL,
+                expires_at TEXT NOT NULL,
+                revoked INTEGER NOT NULL DEFAULT 0,
+                used INTEGER NOT NULL DEFAULT 0
+            )",
+            [],
+        )
+        .expect("Failed to create sessions table");
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS webauthn_credentials (
+                credential_id TEXT PRIMARY KEY,
+                user_id INTEGER NOT NULL,
+                credential_json TEXT NOT NULL
+            )",
+            [],
+        )
+        .expect("Failed to create webauthn_credentials table");
+
+        // Best-effort: older databases won't have this column yet. Ignore the
+        // error when it already exists rather than gating startup on it.
+        let _ = conn.execute(
+            "ALTER TABLE candidates ADD COLUMN role TEXT NOT NULL DEFAULT 'user'",
+            [],
+        );
+
+        // Best-effort: lets pre-signup behavior be recorded against an
+        // anonymous device and later linked to the user it belongs to.
+        let _ = conn.execute(
+            "ALTER TABLE user_behaviors ADD COLUMN device_id TEXT",
+            [],
+        );
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_user_behaviors_device_id ON user_behaviors(device_id)",
+            [],
+        )
+        .expect("Failed to create user_behaviors device_id index");
+
+        // Best-effort: lets a synced posting be traced back to the provider
+        // that supplied it, so a provider's sync only deactivates its own rows.
+        let _ = conn.execute(
+            "ALTER TABLE internships ADD COLUMN source TEXT",
+            [],
+        );
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS application_feedback (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                user_id INTEGER NOT NULL,
+                internship_id INTEGER NOT NULL,
+                outcome TEXT NOT NULL,
+                rating INTEGER,
+                comment TEXT,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .expect("Failed to create application_feedback table");
+    }
+
+    /// Checks out a pooled connection on a blocking thread and runs `f`
+    /// against it. Every public method is a thin wrapper around this so none
+    /// of them can stall the async executor on SQLite I/O.
+    async fn with_conn<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = pool.get()?;
+            f(&conn)
+        })
+        .await?
     }
 
     pub async fn get_user_by_email(&self, email: &str) -> Result<User> {
-        let conn = self.conn.lock().await;
-        let mut stmt = conn.prepare(
-            "SELECT id, email, password_hash, name, education, skills, location, 
-             experience_years, phone, linkedin, github, data_consent 
-             FROM candidates WHERE email = ?"
-        )?;
-
-        let user = stmt.query_row([email], |row| {
-            Ok(User {
-                id: row.get(0)?,
-                email: row.get(1)?,
-                password_hash: row.get(2)?,
-                name: row.get(3)?,
-                education: row.get(4)?,
-                skills: row.get(5)?,
-                location: row.get(6)?,
-                experience_years: row.get(7)?,
-                phone: row.get(8)?,
-                linkedin: row.get(9)?,
-                github: row.get(10)?,
-                data_consent: row.get(11)?,
-            })
-        })?;
-
-        Ok(user)
+        let email = email.to_string();
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, email, password_hash, name, education, skills, location,
+                 experience_years, phone, linkedin, github, data_consent
+                 FROM candidates WHERE email = ?"
+            )?;
+
+            let user = stmt.query_row([&email], |row| {
+                Ok(User {
+                    id: row.get(0)?,
+                    email: row.get(1)?,
+                    password_hash: row.get(2)?,
+                    name: row.get(3)?,
+                    education: row.get(4)?,
+                    skills: row.get(5)?,
+                    location: row.get(6)?,
+                    experience_years: row.get(7)?,
+                    phone: row.get(8)?,
+                    linkedin: row.get(9)?,
+                    github: row.get(10)?,
+                    data_consent: row.get(11)?,
+                })
+            })?;
+
+            Ok(user)
+        })
+        .await
     }
 
     pub async fn get_user_by_id(&self, user_id: i32) -> Result<User> {
-        let conn = self.conn.lock().await;
-        let mut stmt = conn.prepare(
-            "SELECT id, email, password_hash, name, education, skills, location, 
-             experience_years, phone, linkedin, github, data_consent 
-             FROM candidates WHERE id = ?"
-        )?;
-
-        let user = stmt.query_row([user_id], |row| {
-            Ok(User {
-                id: row.get(0)?,
-                email: row.get(1)?,
-                password_hash: row.get(2)?,
-                name: row.get(3)?,
-                education: row.get(4)?,
-                skills: row.get(5)?,
-                location: row.get(6)?,
-                experience_years: row.get(7)?,
-                phone: row.get(8)?,
-                linkedin: row.get(9)?,
-                github: row.get(10)?,
-                data_consent: row.get(11)?,
-            })
-        })?;
-
-        Ok(user)
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, email, password_hash, name, education, skills, location,
+                 experience_years, phone, linkedin, github, data_consent
+                 FROM candidates WHERE id = ?"
+            )?;
+
+            let user = stmt.query_row([user_id], |row| {
+                Ok(User {
+                    id: row.get(0)?,
+                    email: row.get(1)?,
+                    password_hash: row.get(2)?,
+                    name: row.get(3)?,
+                    education: row.get(4)?,
+                    skills: row.get(5)?,
+                    location: row.get(6)?,
+                    experience_years: row.get(7)?,
+                    phone: row.get(8)?,
+                    linkedin: row.get(9)?,
+                    github: row.get(10)?,
+                    data_consent: row.get(11)?,
+                })
+            })?;
+
+            Ok(user)
+        })
+        .await
     }
 
     pub async fn get_all_internships(&self) -> Result<Vec<Internship>> {
-        let conn = self.conn.lock().await;
-        let mut stmt = conn.prepare(
-            "SELECT id, title, company, location, description, required_skills, 
-             preferred_skills, duration, stipend, application_deadline, posted_date, 
-             is_active, min_education, experience_required 
-             FROM internships WHERE is_active = 1"
-        )?;
-
-        let rows = stmt.query_map([], |row| {
-            Ok(Internship {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                company: row.get(2)?,
-                location: row.get(3)?,
-                description: row.get(4)?,
-                required_skills: row.get(5)?,
-                preferred_skills: row.get(6)?,
-                duration: row.get(7)?,
-                stipend: row.get(8)?,
-                application_deadline: row.get(9)?,
-                posted_date: row.get(10)?,
-                is_active: row.get(11)?,
-                min_education: row.get(12)?,
-                experience_required: row.get(13)?,
-            })
-        })?;
-
-        let mut internships = Vec::new();
-        for row in rows {
-            internships.push(row?);
-        }
-
-        Ok(internships)
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, title, company, location, description, required_skills,
+                 preferred_skills, duration, stipend, application_deadline, posted_date,
+                 is_active, min_education, experience_required
+                 FROM internships WHERE is_active = 1"
+            )?;
+
+            let rows = stmt.query_map([], |row| {
+                Ok(Internship {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    company: row.get(2)?,
+                    location: row.get(3)?,
+                    description: row.get(4)?,
+                    required_skills: row.get(5)?,
+                    preferred_skills: row.get(6)?,
+                    duration: row.get(7)?,
+                    stipend: row.get(8)?,
+                    application_deadline: row.get(9)?,
+                    posted_date: row.get(10)?,
+                    is_active: row.get(11)?,
+                    min_education: row.get(12)?,
+                    experience_required: row.get(13)?,
+                })
+            })?;
+
+            let mut internships = Vec::new();
+            for row in rows {
+                internships.push(row?);
+            }
+
+            Ok(internships)
+        })
+        .await
     }
 
     pub async fn get_user_behaviors(&self, user_id: i32) -> Result<Vec<serde_json::Value>> {
-        let conn = self.conn.lock().await;
-        let mut stmt = conn.prepare(
-            "SELECT behavior_data FROM user_behaviors WHERE user_id = ?"
-        )?;
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT behavior_data FROM user_behaviors WHERE user_id = ?"
+            )?;
 
-        let rows = stmt.query_map([user_id], |row| {
-            let data: String = row.get(0)?;
-            Ok(serde_json::from_str::<serde_json::Value>(&data).unwrap_or(serde_json::Value::Null))
-        })?;
+            let rows = stmt.query_map([user_id], |row| {
+                let data: String = row.get(0)?;
+                Ok(serde_json::from_str::<serde_json::Value>(&data).unwrap_or(serde_json::Value::Null))
+            })?;
 
-        let mut behaviors = Vec::new();
-        for row in rows {
-            behaviors.push(row?);
-        }
+            let mut behaviors = Vec::new();
+            for row in rows {
+                behaviors.push(row?);
+            }
 
-        Ok(behaviors)
+            Ok(behaviors)
+        })
+        .await
     }
 
     pub async fn get_historical_applications(&self) -> Result<Vec<serde_json::Value>> {
-        let conn = self.conn.lock().await;
-        let mut stmt = conn.prepare(
-            "SELECT id, candidate_id, internship_id, applied_at, status FROM applications"
-        )?;
-
-        let rows = stmt.query_map([], |row| {
-            Ok(serde_json::json!({
-                "id": row.get::<_, i32>(0)?,
-                "candidate_id": row.get::<_, i32>(1)?,
-                "internship_id": row.get::<_, i32>(2)?,
-                "applied_at": row.get::<_, String>(3)?,
-                "status": row.get::<_, String>(4)?
-            }))
-        })?;
-
-        let mut applications = Vec::new();
-        for row in rows {
-            applications.push(row?);
-        }
-
-        Ok(applications)
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, candidate_id, internship_id, applied_at, status FROM applications"
+            )?;
+
+            let rows = stmt.query_map([], |row| {
+                Ok(serde_json::json!({
+                    "id": row.get::<_, i32>(0)?,
+                    "candidate_id": row.get::<_, i32>(1)?,
+                    "internship_id": row.get::<_, i32>(2)?,
+                    "applied_at": row.get::<_, String>(3)?,
+                    "status": row.get::<_, String>(4)?
+                }))
+            })?;
+
+            let mut applications = Vec::new();
+            for row in rows {
+                applications.push(row?);
+            }
+
+            Ok(applications)
+        })
+        .await
+    }
+
+    /// Returns `(user_id, skills_csv)` for every candidate with a non-empty
+    /// `skills` field, used as the basis for Jaccard similarity and skill
+    /// co-occurrence in collaborative filtering.
+    pub async fn get_all_user_skills(&self) -> Result<Vec<(i32, String)>> {
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, skills FROM candidates WHERE skills IS NOT NULL AND skills != ''",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?))
+            })?;
+
+            let mut users = Vec::new();
+            for row in rows {
+                users.push(row?);
+            }
+            Ok(users)
+        })
+        .await
+    }
+
+    /// Returns `(internship_id, title, company, application_count, accepted_count)`
+    /// for every internship with at least one application, joined against
+    /// `applications` and sorted by application count descending.
+    pub async fn get_internship_application_stats(&self) -> Result<Vec<(i32, String, String, i32, i32)>> {
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT i.id, i.title, i.company,
+                        COUNT(a.id) AS application_count,
+                        SUM(CASE WHEN a.status = 'accepted' THEN 1 ELSE 0 END) AS accepted_count
+                 FROM internships i
+                 JOIN applications a ON a.internship_id = i.id
+                 GROUP BY i.id, i.title, i.company
+                 ORDER BY application_count DESC",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, i32>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i32>(3)?,
+                    row.get::<_, i32>(4)?,
+                ))
+            })?;
+
+            let mut stats = Vec::new();
+            for row in rows {
+                stats.push(row?);
+            }
+            Ok(stats)
+        })
+        .await
+    }
+
+    /// Returns the comma-separated roles/scopes for a user (e.g. `"user,admin"`),
+    /// defaulting to plain `"user"` for rows from before the `role` column existed.
+    pub async fn get_user_roles(&self, user_id: i32) -> Result<Vec<String>> {
+        self.with_conn(move |conn| {
+            let role: Option<String> = conn
+                .query_row("SELECT role FROM candidates WHERE id = ?", [user_id], |row| row.get(0))
+                .unwrap_or(Some("user".to_string()));
+
+            Ok(role
+                .unwrap_or_else(|| "user".to_string())
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect())
+        })
+        .await
+    }
+
+    /// Stores a newly-registered WebAuthn credential (passkey) for a user. The
+    /// credential itself is persisted as opaque JSON produced by the `webauthn-rs`
+    /// library so its public key, counter, and transport hints travel together.
+    pub async fn add_webauthn_credential(&self, user_id: i32, credential_id: &str, credential_json: &str) -> Result<()> {
+        let credential_id = credential_id.to_string();
+        let credential_json = credential_json.to_string();
+        self.with_conn(move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO webauthn_credentials (credential_id, user_id, credential_json)
+                 VALUES (?, ?, ?)",
+                rusqlite::params![credential_id, user_id, credential_json],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn get_webauthn_credentials(&self, user_id: i32) -> Result<Vec<String>> {
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT credential_json FROM webauthn_credentials WHERE user_id = ?",
+            )?;
+            let rows = stmt.query_map([user_id], |row| row.get::<_, String>(0))?;
+            let mut credentials = Vec::new();
+            for row in rows {
+                credentials.push(row?);
+            }
+            Ok(credentials)
+        })
+        .await
+    }
+
+    /// Persists the updated credential (with its bumped signature counter) after
+    /// a successful login ceremony.
+    pub async fn update_webauthn_credential(&self, credential_id: &str, credential_json: &str) -> Result<()> {
+        let credential_id = credential_id.to_string();
+        let credential_json = credential_json.to_string();
+        self.with_conn(move |conn| {
+            conn.execute(
+                "UPDATE webauthn_credentials SET credential_json = ? WHERE credential_id = ?",
+                rusqlite::params![credential_json, credential_id],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Inserts a new session row for a freshly-issued refresh token.
+    pub async fn create_session(
+        &self,
+        user_id: i32,
+        refresh_token_hash: &str,
+        family_id: &str,
+        device: Option<&str>,
+        created_at: &str,
+        expires_at: &str,
+    ) -> Result<i32> {
+        let refresh_token_hash = refresh_token_hash.to_string();
+        let family_id = family_id.to_string();
+        let device = device.map(|d| d.to_string());
+        let created_at = created_at.to_string();
+        let expires_at = expires_at.to_string();
+        self.with_conn(move |conn| {
+            conn.execute(
+                "INSERT INTO sessions (user_id, refresh_token_hash, family_id, device, created_at, expires_at, revoked, used)
+                 VALUES (?, ?, ?, ?, ?, ?, 0, 0)",
+                rusqlite::params![user_id, refresh_token_hash, family_id, device, created_at, expires_at],
+            )?;
+            Ok(conn.last_insert_rowid() as i32)
+        })
+        .await
+    }
+
+    pub async fn get_session_by_hash(&self, refresh_token_hash: &str) -> Result<Session> {
+        let refresh_token_hash = refresh_token_hash.to_string();
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, user_id, refresh_token_hash, family_id, device, created_at, expires_at, revoked, used
+                 FROM sessions WHERE refresh_token_hash = ?",
+            )?;
+            let session = stmt.query_row([&refresh_token_hash], |row| {
+                Ok(Session {
+                    id: row.get(0)?,
+                    user_id: row.get(1)?,
+                    refresh_token_hash: row.get(2)?,
+                    family_id: row.get(3)?,
+                    device: row.get(4)?,
+                    created_at: row.get(5)?,
+                    expires_at: row.get(6)?,
+                    revoked: row.get(7)?,
+                    used: row.get(8)?,
+                })
+            })?;
+            Ok(session)
+        })
+        .await
+    }
+
+    pub async fn mark_session_used(&self, session_id: i32) -> Result<()> {
+        self.with_conn(move |conn| {
+            conn.execute("UPDATE sessions SET used = 1 WHERE id = ?", [session_id])?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Revokes every session sharing `family_id` — used when a rotated refresh
+    /// token is replayed, which signals the family may have been stolen.
+    pub async fn revoke_session_family(&self, family_id: &str) -> Result<()> {
+        let family_id = family_id.to_string();
+        self.with_conn(move |conn| {
+            conn.execute("UPDATE sessions SET revoked = 1 WHERE family_id = ?", [&family_id])?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn revoke_session(&self, session_id: i32, user_id: i32) -> Result<()> {
+        self.with_conn(move |conn| {
+            let updated = conn.execute(
+                "UPDATE sessions SET revoked = 1 WHERE id = ? AND user_id = ?",
+                rusqlite::params![session_id, user_id],
+            )?;
+            if updated == 0 {
+                return Err(anyhow::anyhow!("Session not found"));
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn list_sessions(&self, user_id: i32) -> Result<Vec<Session>> {
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, user_id, refresh_token_hash, family_id, device, created_at, expires_at, revoked, used
+                 FROM sessions WHERE user_id = ? AND revoked = 0 AND used = 0 AND expires_at > ?
+                 ORDER BY created_at DESC",
+            )?;
+            let now = chrono::Utc::now().to_rfc3339();
+            let rows = stmt.query_map(rusqlite::params![user_id, now], |row| {
+                Ok(Session {
+                    id: row.get(0)?,
+                    user_id: row.get(1)?,
+                    refresh_token_hash: row.get(2)?,
+                    family_id: row.get(3)?,
+                    device: row.get(4)?,
+                    created_at: row.get(5)?,
+                    expires_at: row.get(6)?,
+                    revoked: row.get(7)?,
+                    used: row.get(8)?,
+                })
+            })?;
+
+            let mut sessions = Vec::new();
+            for row in rows {
+                sessions.push(row?);
+            }
+            Ok(sessions)
+        })
+        .await
+    }
+
+    /// Updates a user's stored password hash, used to silently migrate legacy
+    /// bcrypt hashes to Argon2id as users log in.
+    pub async fn update_password_hash(&self, user_id: i32, new_hash: &str) -> Result<()> {
+        let new_hash = new_hash.to_string();
+        self.with_conn(move |conn| {
+            conn.execute(
+                "UPDATE candidates SET password_hash = ? WHERE id = ?",
+                rusqlite::params![new_hash, user_id],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Inserts a newly-seen posting or refreshes an existing one, deduplicated
+    /// by `(title, company)` — the key external job-board providers don't give
+    /// us a stable cross-provider ID for. Reactivates the row if it had
+    /// previously been marked stale. `source` records which provider supplied
+    /// the posting, so a later sync only ever deactivates rows it owns.
+    pub async fn upsert_internship(
+        &self,
+        title: &str,
+        company: &str,
+        location: Option<&str>,
+        required_skills: Option<&str>,
+        preferred_skills: Option<&str>,
+        application_deadline: Option<&str>,
+        source: &str,
+    ) -> Result<i32> {
+        let title = title.to_string();
+        let company = company.to_string();
+        let location = location.map(|s| s.to_string());
+        let required_skills = required_skills.map(|s| s.to_string());
+        let preferred_skills = preferred_skills.map(|s| s.to_string());
+        let application_deadline = application_deadline.map(|s| s.to_string());
+        let source = source.to_string();
+
+        self.with_conn(move |conn| {
+            let existing: Option<i32> = conn
+                .query_row(
+                    "SELECT id FROM internships WHERE title = ? AND company = ?",
+                    rusqlite::params![title, company],
+                    |row| row.get(0),
+                )
+                .optional()?;
+
+            if let Some(id) = existing {
+                conn.execute(
+                    "UPDATE internships
+                     SET location = ?, required_skills = ?, preferred_skills = ?, application_deadline = ?, is_active = 1, source = ?
+                     WHERE id = ?",
+                    rusqlite::params![location, required_skills, preferred_skills, application_deadline, source, id],
+                )?;
+                Ok(id)
+            } else {
+                conn.execute(
+                    "INSERT INTO internships
+                     (title, company, location, required_skills, preferred_skills, application_deadline, posted_date, is_active, experience_required, source)
+                     VALUES (?, ?, ?, ?, ?, ?, date('now'), 1, 0, ?)",
+                    rusqlite::params![title, company, location, required_skills, preferred_skills, application_deadline, source],
+                )?;
+                Ok(conn.last_insert_rowid() as i32)
+            }
+        })
+        .await
+    }
+
+    /// Marks every currently-active internship sourced from `source` whose
+    /// `(title, company)` isn't in `seen` as inactive — called after a
+    /// provider sync with the set of postings still present in that
+    /// provider's feed, so listings the provider has removed stop being
+    /// recommended without touching other providers' (or manually seeded) rows.
+    pub async fn deactivate_missing_internships(&self, source: &str, seen: Vec<(String, String)>) -> Result<usize> {
+        let source = source.to_string();
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare("SELECT id, title, company FROM internships WHERE is_active = 1 AND source = ?")?;
+            let rows: Vec<(i32, String, String)> = stmt
+                .query_map(rusqlite::params![source], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+                .collect::<rusqlite::Result<_>>()?;
+
+            let seen: HashSet<(String, String)> = seen.into_iter().collect();
+            let mut deactivated = 0;
+            for (id, title, company) in rows {
+                if !seen.contains(&(title, company)) {
+                    conn.execute("UPDATE internships SET is_active = 0 WHERE id = ?", [id])?;
+                    deactivated += 1;
+                }
+            }
+            Ok(deactivated)
+        })
+        .await
+    }
+
+    /// Records a user's outcome report (interview reached, offer, rejected,
+    /// withdrew) for one of their applications, with an optional rating/comment.
+    /// Fails if `user_id` has no application on record for `internship_id`, so
+    /// feedback can't be submitted against internships the caller never applied to.
+    pub async fn record_application_feedback(
+        &self,
+        user_id: i32,
+        internship_id: i32,
+        outcome: &str,
+        rating: Option<i32>,
+        comment: Option<&str>,
+        created_at: &str,
+    ) -> Result<i32> {
+        let outcome = outcome.to_string();
+        let comment = comment.map(|s| s.to_string());
+        let created_at = created_at.to_string();
+        self.with_conn(move |conn| {
+            let has_application: Option<i32> = conn
+                .query_row(
+                    "SELECT 1 FROM applications WHERE candidate_id = ? AND internship_id = ? LIMIT 1",
+                    rusqlite::params![user_id, internship_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if has_application.is_none() {
+                return Err(anyhow::anyhow!(
+                    "No application on record for user {user_id} and internship {internship_id}"
+                ));
+            }
+
+            conn.execute(
+                "INSERT INTO application_feedback (user_id, internship_id, outcome, rating, comment, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+                rusqlite::params![user_id, internship_id, outcome, rating, comment, created_at],
+            )?;
+            Ok(conn.last_insert_rowid() as i32)
+        })
+        .await
+    }
+
+    pub async fn list_feedback_for_user(&self, user_id: i32) -> Result<Vec<ApplicationFeedback>> {
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, user_id, internship_id, outcome, rating, comment, created_at
+                 FROM application_feedback WHERE user_id = ? ORDER BY created_at DESC",
+            )?;
+            let rows = stmt.query_map([user_id], map_application_feedback_row)?;
+
+            let mut feedback = Vec::new();
+            for row in rows {
+                feedback.push(row?);
+            }
+            Ok(feedback)
+        })
+        .await
+    }
+
+    pub async fn list_feedback_for_internship(&self, internship_id: i32) -> Result<Vec<ApplicationFeedback>> {
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, user_id, internship_id, outcome, rating, comment, created_at
+                 FROM application_feedback WHERE internship_id = ? ORDER BY created_at DESC",
+            )?;
+            let rows = stmt.query_map([internship_id], map_application_feedback_row)?;
+
+            let mut feedback = Vec::new();
+            for row in rows {
+                feedback.push(row?);
+            }
+            Ok(feedback)
+        })
+        .await
+    }
+
+    /// Returns `(internship_id, feedback_count, offer_count)` for every
+    /// internship with at least one piece of feedback, the basis for a
+    /// feedback-driven `success_rate` in `PopularInternship`.
+    pub async fn get_internship_feedback_stats(&self) -> Result<Vec<(i32, i32, i32)>> {
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT internship_id, COUNT(*), SUM(CASE WHEN outcome = 'offer' THEN 1 ELSE 0 END)
+                 FROM application_feedback
+                 GROUP BY internship_id",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, i32>(0)?, row.get::<_, i32>(1)?, row.get::<_, i32>(2)?))
+            })?;
+
+            let mut stats = Vec::new();
+            for row in rows {
+                stats.push(row?);
+            }
+            Ok(stats)
+        })
+        .await
+    }
+
+    /// Returns `(applied_at, company, required_skills, preferred_skills)` for
+    /// every application, joined against its internship, so skill and company
+    /// trends can be bucketed into time windows in Rust.
+    pub async fn get_application_skill_timestamps(
+        &self,
+    ) -> Result<Vec<(String, String, Option<String>, Option<String>)>> {
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT a.applied_at, i.company, i.required_skills, i.preferred_skills
+                 FROM applications a
+                 JOIN internships i ON a.internship_id = i.id",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })?;
+
+            let mut out = Vec::new();
+            for row in rows {
+                out.push(row?);
+            }
+            Ok(out)
+        })
+        .await
+    }
+
+    /// Inserts one behavior event for `device_id`, optionally tagged with the
+    /// authenticated `user_id` at write time.
+    pub async fn insert_behavior_event(
+        &self,
+        device_id: &str,
+        user_id: Option<i32>,
+        behavior_data: &str,
+    ) -> Result<i32> {
+        let device_id = device_id.to_string();
+        let behavior_data = behavior_data.to_string();
+        self.with_conn(move |conn| {
+            conn.execute(
+                "INSERT INTO user_behaviors (user_id, device_id, behavior_data) VALUES (?, ?, ?)",
+                rusqlite::params![user_id, device_id, behavior_data],
+            )?;
+            Ok(conn.last_insert_rowid() as i32)
+        })
+        .await
+    }
+
+    /// Back-fills `user_id` onto every anonymous (`user_id IS NULL`) behavior
+    /// row recorded for `device_id`, attributing pre-signup browsing to the
+    /// account that just authenticated from that device.
+    pub async fn link_device(&self, device_id: &str, user_id: i32) -> Result<()> {
+        let device_id = device_id.to_string();
+        self.with_conn(move |conn| {
+            conn.execute(
+                "UPDATE user_behaviors SET user_id = ? WHERE device_id = ? AND user_id IS NULL",
+                rusqlite::params![user_id, device_id],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Provisions a new `User` row for a federated SSO login that has no matching
+    /// local account yet. SSO-provisioned users have no local password, so
+    /// `password_hash` is set to an empty string (never a valid bcrypt/Argon2 hash,
+    /// so a direct password login can never match it).
+    pub async fn provision_sso_user(&self, email: &str) -> Result<i32> {
+        let email = email.to_string();
+        self.with_conn(move |conn| {
+            let name = email.split('@').next().unwrap_or(&email).to_string();
+            conn.execute(
+                "INSERT INTO candidates (email, password_hash, name, experience_years) VALUES (?, '', ?, 0)",
+                rusqlite::params![email, name],
+            )?;
+            Ok(conn.last_insert_rowid() as i32)
+        })
+        .await
     }
 }
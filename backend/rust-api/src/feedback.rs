@@ -0,0 +1,47 @@
+use crate::database::DatabaseService;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const VALID_OUTCOMES: [&str; 4] = ["interview_reached", "offer", "rejected", "withdrew"];
+
+#[derive(Debug, Deserialize, Serialize, utoipa::ToSchema)]
+pub struct SubmitFeedbackRequest {
+    pub internship_id: i32,
+    pub outcome: String,
+    pub rating: Option<i32>,
+    pub comment: Option<String>,
+}
+
+/// Records per-application outcome feedback (interview reached, offer,
+/// rejected, withdrew) with an optional rating/comment — the structured
+/// signal `InsightsService` uses to compute real success rates instead of
+/// inferring them from a bare `applications.status` string.
+pub struct FeedbackService {
+    db: Arc<DatabaseService>,
+}
+
+impl FeedbackService {
+    pub fn new(db: Arc<DatabaseService>) -> Self {
+        Self { db }
+    }
+
+    pub async fn submit_feedback(&self, user_id: i32, request: &SubmitFeedbackRequest) -> Result<()> {
+        if !VALID_OUTCOMES.contains(&request.outcome.as_str()) {
+            return Err(anyhow!("Invalid outcome: {}", request.outcome));
+        }
+
+        let created_at = chrono::Utc::now().to_rfc3339();
+        self.db
+            .record_application_feedback(
+                user_id,
+                request.internship_id,
+                &request.outcome,
+                request.rating,
+                request.comment.as_deref(),
+                &created_at,
+            )
+            .await?;
+        Ok(())
+    }
+}
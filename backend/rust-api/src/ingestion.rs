@@ -0,0 +1,170 @@
+use crate::database::DatabaseService;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// How close to actual expiry a cached client-credentials token is refreshed,
+/// so an in-flight sync never hands a request a token that expires mid-call.
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(5);
+
+/// Static configuration for a single external job-board provider, loaded from env.
+#[derive(Debug, Clone)]
+struct IngestionProviderConfig {
+    client_id: String,
+    client_secret: String,
+    token_endpoint: String,
+    listings_endpoint: String,
+}
+
+impl IngestionProviderConfig {
+    /// Loads provider config from `INGEST_{PROVIDER}_*` env vars. Returns `None`
+    /// when the provider isn't configured, so unconfigured providers are simply
+    /// skipped during sync.
+    fn from_env(provider: &str) -> Option<Self> {
+        let prefix = format!("INGEST_{}", provider.to_uppercase());
+        Some(Self {
+            client_id: std::env::var(format!("{prefix}_CLIENT_ID")).ok()?,
+            client_secret: std::env::var(format!("{prefix}_CLIENT_SECRET")).ok()?,
+            token_endpoint: std::env::var(format!("{prefix}_TOKEN_ENDPOINT")).ok()?,
+            listings_endpoint: std::env::var(format!("{prefix}_LISTINGS_ENDPOINT")).ok()?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ClientCredentialsResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Debug, Clone)]
+struct CachedToken {
+    access_token: String,
+    expires_at: Instant,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListingsResponse {
+    jobs: Vec<ProviderListing>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderListing {
+    title: String,
+    company: String,
+    location: Option<String>,
+    required_skills: Option<String>,
+    preferred_skills: Option<String>,
+    application_deadline: Option<String>,
+}
+
+/// Pulls internship postings from external provider job boards via an OAuth2
+/// client-credentials flow and upserts them into `internships`. Known
+/// providers are registered from env at startup; each provider's token is
+/// cached in memory and transparently refreshed once it's within a few
+/// seconds of expiring.
+pub struct IngestionService {
+    db: Arc<DatabaseService>,
+    http: reqwest::Client,
+    providers: HashMap<String, IngestionProviderConfig>,
+    tokens: Mutex<HashMap<String, CachedToken>>,
+}
+
+impl IngestionService {
+    pub fn new(db: Arc<DatabaseService>) -> Self {
+        let mut providers = HashMap::new();
+        for name in ["indeed", "linkedin", "handshake"] {
+            if let Some(cfg) = IngestionProviderConfig::from_env(name) {
+                providers.insert(name.to_string(), cfg);
+            }
+        }
+
+        Self {
+            db,
+            http: reqwest::Client::new(),
+            providers,
+            tokens: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns a valid access token for `provider`, fetching or refreshing it
+    /// against the token endpoint as needed.
+    async fn access_token(&self, provider: &str, config: &IngestionProviderConfig) -> Result<String> {
+        {
+            let tokens = self.tokens.lock().await;
+            if let Some(cached) = tokens.get(provider) {
+                if cached.expires_at > Instant::now() + TOKEN_EXPIRY_MARGIN {
+                    return Ok(cached.access_token.clone());
+                }
+            }
+        }
+
+        let response: ClientCredentialsResponse = self
+            .http
+            .post(&config.token_endpoint)
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", &config.client_id),
+                ("client_secret", &config.client_secret),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let cached = CachedToken {
+            access_token: response.access_token.clone(),
+            expires_at: Instant::now() + Duration::from_secs(response.expires_in),
+        };
+        self.tokens.lock().await.insert(provider.to_string(), cached);
+
+        Ok(response.access_token)
+    }
+
+    /// Fetches `provider`'s current listings, upserts each into `internships`
+    /// by `(title, company)`, then deactivates any previously-active posting
+    /// from this provider that no longer appears in the feed. Returns the
+    /// number of listings synced.
+    pub async fn sync_provider(&self, provider: &str) -> Result<usize> {
+        let config = self
+            .providers
+            .get(provider)
+            .ok_or_else(|| anyhow!("Unknown ingestion provider: {provider}"))?
+            .clone();
+
+        let access_token = self.access_token(provider, &config).await?;
+
+        let listings: ListingsResponse = self
+            .http
+            .get(&config.listings_endpoint)
+            .bearer_auth(access_token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut seen = Vec::with_capacity(listings.jobs.len());
+        for job in &listings.jobs {
+            self.db
+                .upsert_internship(
+                    &job.title,
+                    &job.company,
+                    job.location.as_deref(),
+                    job.required_skills.as_deref(),
+                    job.preferred_skills.as_deref(),
+                    job.application_deadline.as_deref(),
+                    provider,
+                )
+                .await?;
+            seen.push((job.title.clone(), job.company.clone()));
+        }
+
+        self.db.deactivate_missing_internships(provider, seen).await?;
+        Ok(listings.jobs.len())
+    }
+}
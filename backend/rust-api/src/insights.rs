@@ -1,17 +1,17 @@
 use crate::database::DatabaseService;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct UserInsightsResponse {
     pub success: bool,
     pub insights: UserInsights,
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct UserInsights {
     pub total_interactions: i32,
     pub action_breakdown: HashMap<String, i32>,
@@ -22,14 +22,14 @@ pub struct UserInsights {
     pub learning_recommendations: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct MarketInsightsResponse {
     pub success: bool,
     pub insights: MarketInsights,
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct MarketInsights {
     pub total_applications: i32,
     pub success_rate: f64,
@@ -38,28 +38,28 @@ pub struct MarketInsights {
     pub location_distribution: HashMap<String, i32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct CollaborativeInsightsResponse {
     pub success: bool,
     pub insights: CollaborativeInsights,
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct CollaborativeInsights {
     pub similar_users: Vec<SimilarUser>,
     pub popular_internships: Vec<PopularInternship>,
     pub skill_correlations: HashMap<String, Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct SimilarUser {
     pub user_id: i32,
     pub similarity_score: f64,
     pub common_skills: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct PopularInternship {
     pub internship_id: i32,
     pub title: String,
@@ -68,20 +68,34 @@ pub struct PopularInternship {
     pub success_rate: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct TrendingSkillsResponse {
     pub success: bool,
     pub skills: Vec<TrendingSkill>,
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct TrendingSkill {
     pub skill: String,
     pub frequency: i32,
     pub growth_rate: f64,
 }
 
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FeedbackInsightsResponse {
+    pub success: bool,
+    pub insights: FeedbackInsights,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FeedbackInsights {
+    pub total_feedback: i32,
+    pub outcome_breakdown: HashMap<String, i32>,
+    pub average_rating: Option<f64>,
+}
+
 pub struct InsightsService {
     db: Arc<DatabaseService>,
 }
@@ -124,9 +138,16 @@ impl InsightsService {
             }
         }
         
-        // Calculate success rate (mock data for now)
-        let application_success_rate = 0.75;
-        
+        // Success rate from the user's own outcome feedback (offer / total
+        // reported outcomes), rather than a guess — 0.0 until they've reported any.
+        let feedback = self.db.list_feedback_for_user(user_id).await?;
+        let application_success_rate = if feedback.is_empty() {
+            0.0
+        } else {
+            let offers = feedback.iter().filter(|f| f.outcome == "offer").count();
+            offers as f64 / feedback.len() as f64
+        };
+
         // Generate learning recommendations
         let learning_recommendations = vec![
             "Focus on Python and Machine Learning skills".to_string(),
@@ -154,37 +175,37 @@ impl InsightsService {
     pub async fn get_market_insights(&self) -> Result<MarketInsightsResponse> {
         // Get historical applications
         let applications = self.db.get_historical_applications().await?;
-        
+
         let total_applications = applications.len() as i32;
         let successful_applications = applications.iter()
             .filter(|app| app.get("status").and_then(|v| v.as_str()) == Some("accepted"))
             .count() as i32;
-        
+
         let success_rate = if total_applications > 0 {
             successful_applications as f64 / total_applications as f64
         } else {
             0.0
         };
-        
+
+        let timestamped = self.db.get_application_skill_timestamps().await?;
+        let today = chrono::Utc::now().date_naive();
+
+        // Companies are counted over the combined recent+previous window so a
+        // quiet month doesn't drop an otherwise-popular company off the list;
+        // skills are ranked by the same recent-vs-previous trend as `get_trending_skills`.
+        let popular_companies = count_companies_within(&timestamped, today, TREND_WINDOW_DAYS * 2);
+        let trending_skills = compute_trending_skills(&timestamped, today, TREND_WINDOW_DAYS)
+            .into_iter()
+            .take(5)
+            .map(|skill| skill.skill)
+            .collect();
+
         // Mock data for demonstration
-        let mut popular_companies = HashMap::new();
-        popular_companies.insert("Google".to_string(), 45);
-        popular_companies.insert("Microsoft".to_string(), 38);
-        popular_companies.insert("Amazon".to_string(), 32);
-        
-        let trending_skills = vec![
-            "Python".to_string(),
-            "Machine Learning".to_string(),
-            "React".to_string(),
-            "AWS".to_string(),
-            "Docker".to_string(),
-        ];
-        
         let mut location_distribution = HashMap::new();
         location_distribution.insert("San Francisco".to_string(), 120);
         location_distribution.insert("New York".to_string(), 95);
         location_distribution.insert("Seattle".to_string(), 78);
-        
+
         let insights = MarketInsights {
             total_applications,
             success_rate,
@@ -192,7 +213,7 @@ impl InsightsService {
             trending_skills,
             location_distribution,
         };
-        
+
         Ok(MarketInsightsResponse {
             success: true,
             insights,
@@ -200,48 +221,73 @@ impl InsightsService {
         })
     }
 
-    pub async fn get_collaborative_insights(&self) -> Result<CollaborativeInsightsResponse> {
-        // Mock collaborative insights for demonstration
-        let similar_users = vec![
-            SimilarUser {
-                user_id: 123,
-                similarity_score: 0.85,
-                common_skills: vec!["Python".to_string(), "Machine Learning".to_string()],
-            },
-            SimilarUser {
-                user_id: 456,
-                similarity_score: 0.78,
-                common_skills: vec!["React".to_string(), "JavaScript".to_string()],
-            },
-        ];
-        
-        let popular_internships = vec![
-            PopularInternship {
-                internship_id: 1,
-                title: "Software Engineering Intern".to_string(),
-                company: "Google".to_string(),
-                application_count: 150,
-                success_rate: 0.12,
-            },
-            PopularInternship {
-                internship_id: 2,
-                title: "Data Science Intern".to_string(),
-                company: "Microsoft".to_string(),
-                application_count: 120,
-                success_rate: 0.15,
-            },
-        ];
-        
-        let mut skill_correlations = HashMap::new();
-        skill_correlations.insert("Python".to_string(), vec!["Machine Learning".to_string(), "Data Science".to_string()]);
-        skill_correlations.insert("React".to_string(), vec!["JavaScript".to_string(), "Node.js".to_string()]);
-        
+    pub async fn get_collaborative_insights(&self, user_id: i32) -> Result<CollaborativeInsightsResponse> {
+        let all_skills = self.db.get_all_user_skills().await?;
+        let skill_sets: HashMap<i32, HashSet<String>> = all_skills
+            .into_iter()
+            .map(|(id, skills)| (id, parse_skill_set(&skills)))
+            .collect();
+
+        let empty = HashSet::new();
+        let my_skills = skill_sets.get(&user_id).unwrap_or(&empty);
+
+        let mut similar_users: Vec<SimilarUser> = skill_sets
+            .iter()
+            .filter(|(&other_id, _)| other_id != user_id)
+            .filter_map(|(&other_id, other_skills)| {
+                if my_skills.is_empty() || other_skills.is_empty() {
+                    return None;
+                }
+                let intersection: HashSet<&String> = my_skills.intersection(other_skills).collect();
+                let union_size = my_skills.union(other_skills).count();
+                if union_size == 0 {
+                    return None;
+                }
+                let similarity_score = intersection.len() as f64 / union_size as f64;
+                Some(SimilarUser {
+                    user_id: other_id,
+                    similarity_score,
+                    common_skills: intersection.into_iter().cloned().collect(),
+                })
+            })
+            .collect();
+        similar_users.sort_by(|a, b| b.similarity_score.partial_cmp(&a.similarity_score).unwrap());
+        similar_users.truncate(10);
+
+        let stats = self.db.get_internship_application_stats().await?;
+        let feedback_stats: HashMap<i32, (i32, i32)> = self
+            .db
+            .get_internship_feedback_stats()
+            .await?
+            .into_iter()
+            .map(|(internship_id, total, offers)| (internship_id, (total, offers)))
+            .collect();
+
+        let popular_internships: Vec<PopularInternship> = stats
+            .into_iter()
+            .map(|(internship_id, title, company, application_count, _accepted_count)| {
+                let success_rate = match feedback_stats.get(&internship_id) {
+                    Some(&(total, offers)) if total > 0 => offers as f64 / total as f64,
+                    _ => 0.0,
+                };
+                PopularInternship {
+                    internship_id,
+                    title,
+                    company,
+                    application_count,
+                    success_rate,
+                }
+            })
+            .collect();
+
+        let skill_correlations = compute_skill_correlations(skill_sets.values(), 5);
+
         let insights = CollaborativeInsights {
             similar_users,
             popular_internships,
             skill_correlations,
         };
-        
+
         Ok(CollaborativeInsightsResponse {
             success: true,
             insights,
@@ -250,41 +296,209 @@ impl InsightsService {
     }
 
     pub async fn get_trending_skills(&self, limit: usize) -> Result<TrendingSkillsResponse> {
-        // Mock trending skills data
-        let skills = vec![
-            TrendingSkill {
-                skill: "Python".to_string(),
-                frequency: 450,
-                growth_rate: 0.25,
-            },
-            TrendingSkill {
-                skill: "Machine Learning".to_string(),
-                frequency: 320,
-                growth_rate: 0.35,
-            },
-            TrendingSkill {
-                skill: "React".to_string(),
-                frequency: 280,
-                growth_rate: 0.18,
-            },
-            TrendingSkill {
-                skill: "AWS".to_string(),
-                frequency: 250,
-                growth_rate: 0.42,
-            },
-            TrendingSkill {
-                skill: "Docker".to_string(),
-                frequency: 200,
-                growth_rate: 0.30,
-            },
-        ];
-        
-        let limited_skills = skills.into_iter().take(limit).collect();
-        
+        let timestamped = self.db.get_application_skill_timestamps().await?;
+        let today = chrono::Utc::now().date_naive();
+
+        let mut skills = compute_trending_skills(&timestamped, today, TREND_WINDOW_DAYS);
+        skills.truncate(limit);
+
         Ok(TrendingSkillsResponse {
             success: true,
-            skills: limited_skills,
+            skills,
             message: "Trending skills retrieved successfully".to_string(),
         })
     }
+
+    /// Aggregates a user's own outcome reports into a breakdown by outcome
+    /// and an average rating, for a feedback history view.
+    pub async fn get_feedback_insights(&self, user_id: i32) -> Result<FeedbackInsightsResponse> {
+        let feedback = self.db.list_feedback_for_user(user_id).await?;
+
+        let total_feedback = feedback.len() as i32;
+        let mut outcome_breakdown = HashMap::new();
+        let mut rating_sum = 0i32;
+        let mut rating_count = 0i32;
+
+        for entry in &feedback {
+            *outcome_breakdown.entry(entry.outcome.clone()).or_insert(0) += 1;
+            if let Some(rating) = entry.rating {
+                rating_sum += rating;
+                rating_count += 1;
+            }
+        }
+
+        let average_rating = if rating_count > 0 {
+            Some(rating_sum as f64 / rating_count as f64)
+        } else {
+            None
+        };
+
+        let insights = FeedbackInsights {
+            total_feedback,
+            outcome_breakdown,
+            average_rating,
+        };
+
+        Ok(FeedbackInsightsResponse {
+            success: true,
+            insights,
+            message: "Feedback insights generated successfully".to_string(),
+        })
+    }
+}
+
+/// Window length (in days) used to compare "recent" skill/company activity
+/// against the immediately preceding window of equal length.
+const TREND_WINDOW_DAYS: i64 = 30;
+
+fn parse_applied_at(applied_at: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(applied_at, "%Y-%m-%d")
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDateTime::parse_from_str(applied_at, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|dt| dt.date())
+        })
+        .or_else(|| {
+            chrono::DateTime::parse_from_rfc3339(applied_at)
+                .ok()
+                .map(|dt| dt.date_naive())
+        })
+}
+
+/// Splits applications' skills into two adjacent, equal-length windows ending
+/// today — `(recent_counts, previous_counts)` — counting each required or
+/// preferred skill once per application.
+fn bucket_skill_windows(
+    rows: &[(String, String, Option<String>, Option<String>)],
+    today: chrono::NaiveDate,
+    window_days: i64,
+) -> (HashMap<String, i32>, HashMap<String, i32>) {
+    let recent_start = today - chrono::Duration::days(window_days);
+    let previous_start = recent_start - chrono::Duration::days(window_days);
+
+    let mut recent = HashMap::new();
+    let mut previous = HashMap::new();
+
+    for (applied_at, _company, required, preferred) in rows {
+        let Some(date) = parse_applied_at(applied_at) else {
+            continue;
+        };
+        let bucket = if date > recent_start && date <= today {
+            &mut recent
+        } else if date > previous_start && date <= recent_start {
+            &mut previous
+        } else {
+            continue;
+        };
+
+        let mut skills = parse_skill_set(required.as_deref().unwrap_or(""));
+        skills.extend(parse_skill_set(preferred.as_deref().unwrap_or("")));
+        for skill in skills {
+            *bucket.entry(skill).or_insert(0) += 1;
+        }
+    }
+
+    (recent, previous)
+}
+
+/// Counts applications per company whose `applied_at` falls within the last
+/// `window_days`.
+fn count_companies_within(
+    rows: &[(String, String, Option<String>, Option<String>)],
+    today: chrono::NaiveDate,
+    window_days: i64,
+) -> HashMap<String, i32> {
+    let start = today - chrono::Duration::days(window_days);
+    let mut counts = HashMap::new();
+    for (applied_at, company, _required, _preferred) in rows {
+        let Some(date) = parse_applied_at(applied_at) else {
+            continue;
+        };
+        if date > start && date <= today {
+            *counts.entry(company.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Buckets skill occurrences into a recent and a preceding window, then
+/// returns every skill seen in either window as a `TrendingSkill` — frequency
+/// is the recent-window count, growth_rate compares it against the previous
+/// window — sorted by a blend of the two so a skill that's both common and
+/// accelerating outranks one that's merely common or merely new.
+fn compute_trending_skills(
+    rows: &[(String, String, Option<String>, Option<String>)],
+    today: chrono::NaiveDate,
+    window_days: i64,
+) -> Vec<TrendingSkill> {
+    let (recent, previous) = bucket_skill_windows(rows, today, window_days);
+
+    let mut skill_names: HashSet<String> = recent.keys().cloned().collect();
+    skill_names.extend(previous.keys().cloned());
+
+    let mut skills: Vec<TrendingSkill> = skill_names
+        .into_iter()
+        .map(|skill| {
+            let frequency = *recent.get(&skill).unwrap_or(&0);
+            let previous_count = *previous.get(&skill).unwrap_or(&0);
+            let growth_rate = (frequency - previous_count) as f64 / previous_count.max(1) as f64;
+            TrendingSkill {
+                skill,
+                frequency,
+                growth_rate,
+            }
+        })
+        .collect();
+
+    skills.sort_by(|a, b| {
+        let score_a = a.frequency as f64 + a.growth_rate;
+        let score_b = b.frequency as f64 + b.growth_rate;
+        score_b.partial_cmp(&score_a).unwrap()
+    });
+
+    skills
+}
+
+fn parse_skill_set(skills_csv: &str) -> HashSet<String> {
+    skills_csv
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Counts how often each pair of skills co-occurs in the same user's skill set,
+/// then returns, for every skill, the top-k other skills by co-occurrence frequency.
+fn compute_skill_correlations<'a>(
+    skill_sets: impl Iterator<Item = &'a HashSet<String>>,
+    top_k: usize,
+) -> HashMap<String, Vec<String>> {
+    let mut co_occurrences: HashMap<String, HashMap<String, i32>> = HashMap::new();
+
+    for skills in skill_sets {
+        let skills: Vec<&String> = skills.iter().collect();
+        for i in 0..skills.len() {
+            for j in 0..skills.len() {
+                if i == j {
+                    continue;
+                }
+                *co_occurrences
+                    .entry(skills[i].clone())
+                    .or_default()
+                    .entry(skills[j].clone())
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    co_occurrences
+        .into_iter()
+        .map(|(skill, counts)| {
+            let mut correlated: Vec<(String, i32)> = counts.into_iter().collect();
+            correlated.sort_by(|a, b| b.1.cmp(&a.1));
+            correlated.truncate(top_k);
+            (skill, correlated.into_iter().map(|(s, _)| s).collect())
+        })
+        .collect()
 }
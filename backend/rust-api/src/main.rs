@@ -11,6 +11,8 @@ use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing::{info, error};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
 
 mod auth;
 mod recommendations;
@@ -18,13 +20,96 @@ mod insights;
 mod database;
 mod python_client;
 mod middleware;
+mod sso;
+mod webauthn;
+mod telemetry;
+mod ingestion;
+mod feedback;
 
-use auth::AuthService;
+use auth::{AuthService, AuthUser, Admin, RequireRole};
 use recommendations::RecommendationService;
 use insights::InsightsService;
 use database::DatabaseService;
 use python_client::PythonClient;
 use middleware::request_logging_middleware;
+use sso::SsoService;
+use webauthn::WebauthnService;
+use telemetry::TelemetryService;
+use ingestion::IngestionService;
+use feedback::FeedbackService;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        login,
+        refresh_token,
+        logout,
+        list_sessions,
+        revoke_session,
+        sso_start,
+        sso_callback,
+        webauthn_register_start,
+        webauthn_register_finish,
+        webauthn_login_start,
+        webauthn_login_finish,
+        track_telemetry_event,
+        sync_ingestion_provider,
+        get_recommendations,
+        get_user_insights,
+        get_market_insights,
+        get_collaborative_insights,
+        get_trending_skills,
+        submit_feedback,
+        get_feedback_insights,
+    ),
+    components(schemas(
+        auth::LoginRequest,
+        auth::LoginResponse,
+        auth::UserInfo,
+        auth::RefreshRequest,
+        auth::RefreshResponse,
+        auth::LogoutRequest,
+        auth::SessionInfo,
+        auth::SessionsResponse,
+        sso::SsoStartResponse,
+        webauthn::LoginStartRequest,
+        telemetry::TrackEventRequest,
+        feedback::SubmitFeedbackRequest,
+        database::Internship,
+        recommendations::RecommendationResponse,
+        recommendations::RecommendationWithDetails,
+        insights::UserInsightsResponse,
+        insights::UserInsights,
+        insights::MarketInsightsResponse,
+        insights::MarketInsights,
+        insights::CollaborativeInsightsResponse,
+        insights::CollaborativeInsights,
+        insights::SimilarUser,
+        insights::PopularInternship,
+        insights::TrendingSkillsResponse,
+        insights::TrendingSkill,
+        insights::FeedbackInsightsResponse,
+        insights::FeedbackInsights,
+    )),
+    modifiers(&SecurityAddon),
+    tags((name = "InternGenie", description = "InternGenie recommendation & insights API"))
+)]
+struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "bearer_auth",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+            );
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct AppState {
@@ -33,6 +118,11 @@ pub struct AppState {
     pub insights_service: Arc<InsightsService>,
     pub database_service: Arc<DatabaseService>,
     pub python_client: Arc<PythonClient>,
+    pub sso_service: Arc<SsoService>,
+    pub webauthn_service: Arc<WebauthnService>,
+    pub telemetry_service: Arc<TelemetryService>,
+    pub ingestion_service: Arc<IngestionService>,
+    pub feedback_service: Arc<FeedbackService>,
 }
 
 #[tokio::main]
@@ -50,6 +140,11 @@ async fn main() {
     let recommendation_service = Arc::new(RecommendationService::new(database_service.clone()));
     let insights_service = Arc::new(InsightsService::new(database_service.clone()));
     let python_client = Arc::new(PythonClient::new("http://localhost:8000"));
+    let sso_service = Arc::new(SsoService::new(database_service.clone()));
+    let webauthn_service = Arc::new(WebauthnService::new(database_service.clone()));
+    let telemetry_service = Arc::new(TelemetryService::new(database_service.clone()));
+    let ingestion_service = Arc::new(IngestionService::new(database_service.clone()));
+    let feedback_service = Arc::new(FeedbackService::new(database_service.clone()));
 
     let app_state = AppState {
         auth_service,
@@ -57,17 +152,44 @@ async fn main() {
         insights_service,
         database_service,
         python_client,
+        sso_service,
+        webauthn_service,
+        telemetry_service,
+        ingestion_service,
+        feedback_service,
     };
 
     // Build the application
     let app = Router::new()
+        // Interactive API docs
+        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+
         // Health check
         .route("/health", get(health_check))
         
         // Authentication endpoints (Rust)
         .route("/auth/login", post(login))
         .route("/auth/refresh", post(refresh_token))
-        
+        .route("/auth/logout", post(logout))
+        .route("/auth/sessions", get(list_sessions))
+        .route("/auth/sessions/:id", axum::routing::delete(revoke_session))
+        .route("/auth/sso/:provider/start", get(sso_start))
+        .route("/auth/sso/:provider/callback", get(sso_callback))
+        .route("/auth/webauthn/register/start", post(webauthn_register_start))
+        .route("/auth/webauthn/register/finish", post(webauthn_register_finish))
+        .route("/auth/webauthn/login/start", post(webauthn_login_start))
+        .route("/auth/webauthn/login/finish", post(webauthn_login_finish))
+
+        // Telemetry ingestion (Rust)
+        .route("/telemetry/events", post(track_telemetry_event))
+
+        // Admin-only external job-board sync
+        .route("/admin/ingestion/:provider/sync", post(sync_ingestion_provider))
+
+        // Post-application feedback
+        .route("/feedback", post(submit_feedback))
+        .route("/feedback-insights", get(get_feedback_insights))
+
         // Recommendation endpoints (Rust)
         .route("/recommendations", get(get_recommendations))
         
@@ -103,24 +225,42 @@ async fn main() {
 }
 
 // Health check endpoint
-async fn health_check() -> Json<serde_json::Value> {
+async fn health_check(State(state): State<AppState>) -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "status": "healthy",
         "service": "rust-api",
-        "timestamp": chrono::Utc::now().to_rfc3339()
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "python_backend": state.python_client.breaker_status()
     }))
 }
 
 // Authentication endpoints
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    request_body = auth::LoginRequest,
+    responses(
+        (status = 200, description = "Login successful", body = auth::LoginResponse),
+        (status = 401, description = "Invalid credentials"),
+    ),
+)]
 async fn login(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<auth::LoginRequest>,
 ) -> Result<Json<auth::LoginResponse>, StatusCode> {
     info!("Login attempt for email: {}", payload.email);
-    
-    match state.auth_service.login(&payload.email, &payload.password).await {
+    let device = headers.get("user-agent").and_then(|h| h.to_str().ok());
+    let device_id = headers.get("x-device-id").and_then(|h| h.to_str().ok());
+
+    match state.auth_service.login(&payload.email, &payload.password, device).await {
         Ok(response) => {
             info!("Login successful for email: {}", payload.email);
+            if let Some(device_id) = device_id {
+                if let Err(e) = state.telemetry_service.link_device(device_id, response.user.id).await {
+                    error!("Failed to link device {} to user {}: {:?}", device_id, response.user.id, e);
+                }
+            }
             Ok(Json(response))
         },
         Err(e) => {
@@ -130,27 +270,325 @@ async fn login(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    request_body = auth::RefreshRequest,
+    responses(
+        (status = 200, description = "Token refreshed", body = auth::RefreshResponse),
+        (status = 401, description = "Refresh token invalid, expired, or reused"),
+    ),
+)]
 async fn refresh_token(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    Json(payload): Json<auth::RefreshRequest>,
 ) -> Result<Json<auth::RefreshResponse>, StatusCode> {
-    let auth_header = headers.get("authorization")
-        .and_then(|h| h.to_str().ok())
-        .ok_or(StatusCode::UNAUTHORIZED)?;
-    
-    match state.auth_service.refresh_token(auth_header).await {
+    match state.auth_service.refresh_token(&payload.refresh_token).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            error!("Token refresh failed: {:?}", e);
+            Err(StatusCode::UNAUTHORIZED)
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    request_body = auth::LogoutRequest,
+    responses(
+        (status = 200, description = "Logged out"),
+        (status = 401, description = "Refresh token not found"),
+    ),
+)]
+async fn logout(
+    State(state): State<AppState>,
+    Json(payload): Json<auth::LogoutRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .auth_service
+        .logout(&payload.refresh_token)
+        .await
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    Ok(Json(serde_json::json!({ "success": true, "message": "Logged out" })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/auth/sessions",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Active sessions for the caller", body = auth::SessionsResponse)),
+)]
+async fn list_sessions(
+    State(state): State<AppState>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<auth::SessionsResponse>, StatusCode> {
+    state
+        .auth_service
+        .list_sessions(claims.user_id)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/auth/sessions/{id}",
+    security(("bearer_auth" = [])),
+    params(("id" = i32, Path, description = "Session id to revoke")),
+    responses(
+        (status = 200, description = "Session revoked"),
+        (status = 404, description = "Session not found"),
+    ),
+)]
+async fn revoke_session(
+    State(state): State<AppState>,
+    AuthUser(claims): AuthUser,
+    Path(session_id): Path<i32>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .auth_service
+        .revoke_session(claims.user_id, session_id)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    Ok(Json(serde_json::json!({ "success": true, "message": "Session revoked" })))
+}
+
+// SSO endpoints
+#[utoipa::path(
+    get,
+    path = "/auth/sso/{provider}/start",
+    params(("provider" = String, Path, description = "SSO provider name")),
+    responses(
+        (status = 200, description = "Authorization URL to redirect the user to", body = sso::SsoStartResponse),
+        (status = 400, description = "Unknown or misconfigured provider"),
+    ),
+)]
+async fn sso_start(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+) -> Result<Json<sso::SsoStartResponse>, StatusCode> {
+    match state.sso_service.start(&provider).await {
         Ok(response) => Ok(Json(response)),
-        Err(_) => Err(StatusCode::UNAUTHORIZED),
+        Err(e) => {
+            error!("SSO start failed for provider {}: {:?}", provider, e);
+            Err(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/auth/sso/{provider}/callback",
+    params(
+        ("provider" = String, Path, description = "SSO provider name"),
+        ("code" = String, Query, description = "Authorization code from the provider"),
+        ("state" = String, Query, description = "Opaque state value from the initial redirect"),
+    ),
+    responses(
+        (status = 200, description = "Login successful", body = auth::LoginResponse),
+        (status = 401, description = "SSO exchange failed"),
+    ),
+)]
+async fn sso_callback(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+    Query(query): Query<sso::SsoCallbackQuery>,
+) -> Result<Json<auth::LoginResponse>, StatusCode> {
+    let user_id = state
+        .sso_service
+        .callback(&provider, &query)
+        .await
+        .map_err(|e| {
+            error!("SSO callback failed for provider {}: {:?}", provider, e);
+            StatusCode::UNAUTHORIZED
+        })?;
+
+    state
+        .auth_service
+        .issue_token_for_user(user_id)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+// WebAuthn / passkey endpoints. The challenge/credential payloads are
+// `webauthn-rs` types that don't implement `utoipa::ToSchema`, so these
+// routes are documented without a response/request body schema.
+//
+// Registration requires an authenticated session: the email a passkey is
+// registered against is always the caller's own (`claims.email`), never a
+// request-supplied one, so knowing a victim's email alone can't be used to
+// register an attacker-controlled key against their account.
+#[utoipa::path(
+    post,
+    path = "/auth/webauthn/register/start",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "WebAuthn registration challenge"),
+        (status = 400, description = "No such user"),
+    ),
+)]
+async fn webauthn_register_start(
+    State(state): State<AppState>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<webauthn::RegisterStartResponse>, StatusCode> {
+    state
+        .webauthn_service
+        .register_start(&claims.email)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            error!("WebAuthn register start failed: {:?}", e);
+            StatusCode::BAD_REQUEST
+        })
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/webauthn/register/finish",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Passkey registered"),
+        (status = 400, description = "Registration ceremony failed"),
+    ),
+)]
+async fn webauthn_register_finish(
+    State(state): State<AppState>,
+    AuthUser(claims): AuthUser,
+    Json(payload): Json<webauthn::RegisterFinishRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if payload.email != claims.email {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    state.webauthn_service.register_finish(&payload).await.map_err(|e| {
+        error!("WebAuthn register finish failed: {:?}", e);
+        StatusCode::BAD_REQUEST
+    })?;
+    Ok(Json(serde_json::json!({ "success": true, "message": "Passkey registered" })))
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/webauthn/login/start",
+    request_body = webauthn::LoginStartRequest,
+    responses(
+        (status = 200, description = "WebAuthn authentication challenge"),
+        (status = 401, description = "No registered passkeys for this user"),
+    ),
+)]
+async fn webauthn_login_start(
+    State(state): State<AppState>,
+    Json(payload): Json<webauthn::LoginStartRequest>,
+) -> Result<Json<webauthn::LoginStartResponse>, StatusCode> {
+    state
+        .webauthn_service
+        .login_start(&payload.email)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            error!("WebAuthn login start failed: {:?}", e);
+            StatusCode::UNAUTHORIZED
+        })
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/webauthn/login/finish",
+    responses(
+        (status = 200, description = "Login successful", body = auth::LoginResponse),
+        (status = 401, description = "Assertion verification failed"),
+    ),
+)]
+async fn webauthn_login_finish(
+    State(state): State<AppState>,
+    Json(payload): Json<webauthn::LoginFinishRequest>,
+) -> Result<Json<auth::LoginResponse>, StatusCode> {
+    let user_id = state.webauthn_service.login_finish(&payload).await.map_err(|e| {
+        error!("WebAuthn login finish failed: {:?}", e);
+        StatusCode::UNAUTHORIZED
+    })?;
+
+    state
+        .auth_service
+        .issue_token_for_user(user_id)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+// Telemetry ingestion: anonymous by default, attributed to a user when an
+// Authorization header is present so logged-in activity is tagged immediately
+// rather than waiting for the next login's device-linking pass.
+#[utoipa::path(
+    post,
+    path = "/telemetry/events",
+    request_body = telemetry::TrackEventRequest,
+    responses((status = 200, description = "Event recorded")),
+)]
+async fn track_telemetry_event(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<telemetry::TrackEventRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let user_id = headers
+        .get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|h| h.strip_prefix("Bearer ").unwrap_or(h))
+        .and_then(|token| state.auth_service.decode_token(token).ok())
+        .map(|claims| claims.user_id);
+
+    state
+        .telemetry_service
+        .track_event(&payload.device_id, user_id, &payload.action, payload.payload)
+        .await
+        .map_err(|e| {
+            error!("Failed to record telemetry event: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+// External job-board ingestion
+#[utoipa::path(
+    post,
+    path = "/admin/ingestion/{provider}/sync",
+    security(("bearer_auth" = [])),
+    params(("provider" = String, Path, description = "Configured job-board provider to sync")),
+    responses(
+        (status = 200, description = "Number of listings synced"),
+        (status = 403, description = "Caller lacks the admin role"),
+        (status = 502, description = "Provider sync failed"),
+    ),
+)]
+async fn sync_ingestion_provider(
+    State(state): State<AppState>,
+    RequireRole(_claims, ..): RequireRole<Admin>,
+    Path(provider): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    match state.ingestion_service.sync_provider(&provider).await {
+        Ok(synced) => Ok(Json(serde_json::json!({ "success": true, "synced": synced }))),
+        Err(e) => {
+            error!("Ingestion sync failed for provider {}: {:?}", provider, e);
+            Err(StatusCode::BAD_GATEWAY)
+        }
     }
 }
 
 // Recommendation endpoints
+#[utoipa::path(
+    get,
+    path = "/recommendations",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Ranked internship recommendations", body = recommendations::RecommendationResponse)),
+)]
 async fn get_recommendations(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    AuthUser(claims): AuthUser,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<Json<recommendations::RecommendationResponse>, StatusCode> {
-    let user_id = extract_user_id_from_headers(&headers, &state).await?;
+    let user_id = claims.user_id;
     let limit = params.get("limit").and_then(|s| s.parse().ok()).unwrap_or(5);
     let use_cache = params.get("use_cache").and_then(|s| s.parse().ok()).unwrap_or(true);
     
@@ -169,18 +607,27 @@ async fn get_recommendations(
 }
 
 // Insights endpoints
+#[utoipa::path(
+    get,
+    path = "/user-insights",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Behavioral insights for the caller", body = insights::UserInsightsResponse)),
+)]
 async fn get_user_insights(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    AuthUser(claims): AuthUser,
 ) -> Result<Json<insights::UserInsightsResponse>, StatusCode> {
-    let user_id = extract_user_id_from_headers(&headers, &state).await?;
-    
-    match state.insights_service.get_user_insights(user_id).await {
+    match state.insights_service.get_user_insights(claims.user_id).await {
         Ok(response) => Ok(Json(response)),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/market-insights",
+    responses((status = 200, description = "Aggregate market insights", body = insights::MarketInsightsResponse)),
+)]
 async fn get_market_insights(
     State(state): State<AppState>,
 ) -> Result<Json<insights::MarketInsightsResponse>, StatusCode> {
@@ -190,15 +637,28 @@ async fn get_market_insights(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/collaborative-insights",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Collaborative-filtering insights", body = insights::CollaborativeInsightsResponse)),
+)]
 async fn get_collaborative_insights(
     State(state): State<AppState>,
+    AuthUser(claims): AuthUser,
 ) -> Result<Json<insights::CollaborativeInsightsResponse>, StatusCode> {
-    match state.insights_service.get_collaborative_insights().await {
+    match state.insights_service.get_collaborative_insights(claims.user_id).await {
         Ok(response) => Ok(Json(response)),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/trending-skills",
+    params(("limit" = Option<usize>, Query, description = "Max skills to return")),
+    responses((status = 200, description = "Trending skills with growth rate", body = insights::TrendingSkillsResponse)),
+)]
 async fn get_trending_skills(
     State(state): State<AppState>,
     Query(params): Query<HashMap<String, String>>,
@@ -211,6 +671,51 @@ async fn get_trending_skills(
     }
 }
 
+// Post-application feedback endpoints
+#[utoipa::path(
+    post,
+    path = "/feedback",
+    security(("bearer_auth" = [])),
+    request_body = feedback::SubmitFeedbackRequest,
+    responses(
+        (status = 200, description = "Feedback recorded"),
+        (status = 400, description = "Invalid outcome"),
+    ),
+)]
+async fn submit_feedback(
+    State(state): State<AppState>,
+    AuthUser(claims): AuthUser,
+    Json(payload): Json<feedback::SubmitFeedbackRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    state
+        .feedback_service
+        .submit_feedback(claims.user_id, &payload)
+        .await
+        .map_err(|e| {
+            error!("Failed to submit feedback: {:?}", e);
+            StatusCode::BAD_REQUEST
+        })?;
+    Ok(Json(serde_json::json!({ "success": true, "message": "Feedback recorded" })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/feedback-insights",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Aggregate outcome breakdown for the caller", body = insights::FeedbackInsightsResponse)),
+)]
+async fn get_feedback_insights(
+    State(state): State<AppState>,
+    AuthUser(claims): AuthUser,
+) -> Result<Json<insights::FeedbackInsightsResponse>, StatusCode> {
+    state
+        .insights_service
+        .get_feedback_insights(claims.user_id)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
 // Proxy to Python API for non-migrated endpoints
 async fn proxy_to_python(
     State(state): State<AppState>,
@@ -218,28 +723,16 @@ async fn proxy_to_python(
     Path(path): Path<String>,
     headers: HeaderMap,
     body: Option<axum::body::Bytes>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> impl axum::response::IntoResponse {
     let full_path = format!("/{}", path);
     info!("🔄 Proxying {} {} to Python API", method, full_path);
-    
+
     match state.python_client.proxy_request(method, &full_path, headers, body).await {
-        Ok(response) => Ok(Json(response)),
-        Err(_) => Err(StatusCode::BAD_GATEWAY),
+        Ok(response) => (response.status, Json(response.body)),
+        Err(e) => {
+            error!("Proxy request failed: {:?}", e);
+            (StatusCode::BAD_GATEWAY, Json(serde_json::json!({ "error": "Python backend unreachable" })))
+        }
     }
 }
 
-// Helper function to extract user ID from JWT token
-async fn extract_user_id_from_headers(
-    headers: &HeaderMap,
-    state: &AppState,
-) -> Result<i32, StatusCode> {
-    let auth_header = headers.get("authorization")
-        .and_then(|h| h.to_str().ok())
-        .ok_or(StatusCode::UNAUTHORIZED)?;
-    
-    // Extract token from "Bearer <token>" format
-    let token = auth_header.strip_prefix("Bearer ").unwrap_or(auth_header);
-    
-    state.auth_service.verify_token(token).await
-        .map_err(|_| StatusCode::UNAUTHORIZED)
-}
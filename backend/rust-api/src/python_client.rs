@@ -1,30 +1,160 @@
 use anyhow::Result;
-use axum::http::{HeaderMap, Method};
-use reqwest::{Client, Method as ReqwestMethod};
+use axum::http::{HeaderMap, Method, StatusCode};
+use rand::Rng;
+use reqwest::{Client, Method as ReqwestMethod, StatusCode as ReqwestStatusCode};
 use serde_json::Value;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// A process-lifetime monotonic epoch so breaker timestamps are cheap, overflow-free
+/// u64 millisecond offsets rather than needing a wall-clock `SystemTime`.
+fn epoch() -> Instant {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    *EPOCH.get_or_init(Instant::now)
+}
+
+fn now_millis() -> u64 {
+    epoch().elapsed().as_millis() as u64
+}
+
+/// Failure threshold and cooldown for the circuit breaker guarding the Python backend.
+const FAILURE_THRESHOLD: u32 = 5;
+const OPEN_COOLDOWN: Duration = Duration::from_secs(30);
+const MAX_RETRIES: u32 = 3;
+const BASE_BACKOFF: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// A response forwarded from the Python backend, preserving its original status
+/// code and body instead of collapsing every upstream failure to a flat 502.
+pub struct ProxyResponse {
+    pub status: StatusCode,
+    pub body: Value,
+}
+
+/// Tracks consecutive failures against the Python backend and fast-fails once the
+/// circuit opens, so a slow/flapping Python process can't stall the whole gateway.
+struct CircuitBreaker {
+    consecutive_failures: AtomicU32,
+    opened_at_millis: AtomicU64,
+    half_open_probe_in_flight: AtomicBool,
+}
+
+impl CircuitBreaker {
+    fn new() -> Self {
+        Self {
+            consecutive_failures: AtomicU32::new(0),
+            opened_at_millis: AtomicU64::new(0),
+            half_open_probe_in_flight: AtomicBool::new(false),
+        }
+    }
+
+    fn state(&self) -> CircuitState {
+        let opened_at = self.opened_at_millis.load(Ordering::Relaxed);
+        if opened_at == 0 {
+            return CircuitState::Closed;
+        }
+
+        let elapsed = Duration::from_millis(now_millis().saturating_sub(opened_at));
+        if elapsed >= OPEN_COOLDOWN {
+            CircuitState::HalfOpen
+        } else {
+            CircuitState::Open
+        }
+    }
+
+    /// Whether the caller should actually send this request to the Python backend.
+    /// Closed always allows it and Open never does; HalfOpen lets exactly one
+    /// concurrent caller through as the single probe, fast-failing everyone else
+    /// until that probe's outcome reopens or closes the breaker.
+    fn allow_request(&self) -> bool {
+        match self.state() {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen => self
+                .half_open_probe_in_flight
+                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok(),
+        }
+    }
+
+    fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        self.opened_at_millis.store(0, Ordering::Relaxed);
+        self.half_open_probe_in_flight.store(false, Ordering::Release);
+    }
+
+    fn record_failure(&self) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= FAILURE_THRESHOLD {
+            // 0 means "closed", so never store it as an open timestamp (the
+            // first trip can land in the epoch's first millisecond).
+            self.opened_at_millis.store(now_millis().max(1), Ordering::Relaxed);
+        }
+        self.half_open_probe_in_flight.store(false, Ordering::Release);
+    }
+
+    fn snapshot(&self) -> serde_json::Value {
+        let state = match self.state() {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        };
+        serde_json::json!({
+            "state": state,
+            "consecutive_failures": self.consecutive_failures.load(Ordering::Relaxed),
+        })
+    }
+}
 
 pub struct PythonClient {
     client: Client,
     base_url: String,
+    breaker: CircuitBreaker,
 }
 
 impl PythonClient {
     pub fn new(base_url: &str) -> Self {
+        let client = Client::builder()
+            .connect_timeout(Duration::from_secs(3))
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("Failed to build Python backend HTTP client");
+
         Self {
-            client: Client::new(),
+            client,
             base_url: base_url.to_string(),
+            breaker: CircuitBreaker::new(),
         }
     }
 
+    /// Current breaker state, surfaced on `/health` so operators can see when the
+    /// Python dependency is degraded.
+    pub fn breaker_status(&self) -> serde_json::Value {
+        self.breaker.snapshot()
+    }
+
     pub async fn proxy_request(
         &self,
         method: Method,
         path: &str,
         headers: HeaderMap,
         body: Option<axum::body::Bytes>,
-    ) -> Result<Value> {
+    ) -> Result<ProxyResponse> {
+        if !self.breaker.allow_request() {
+            return Ok(ProxyResponse {
+                status: StatusCode::SERVICE_UNAVAILABLE,
+                body: serde_json::json!({ "error": "Python backend is temporarily unavailable" }),
+            });
+        }
+
         let url = format!("{}{}", self.base_url, path);
-        
         let reqwest_method = match method {
             Method::GET => ReqwestMethod::GET,
             Method::POST => ReqwestMethod::POST,
@@ -32,8 +162,52 @@ impl PythonClient {
             Method::DELETE => ReqwestMethod::DELETE,
             _ => ReqwestMethod::GET,
         };
-        let mut request = self.client.request(reqwest_method, &url);
-        
+
+        // Only idempotent GETs are safe to retry automatically.
+        let retryable = reqwest_method == ReqwestMethod::GET;
+        let max_attempts = if retryable { MAX_RETRIES } else { 1 };
+
+        let mut last_err = None;
+        let mut last_response = None;
+        for attempt in 0..max_attempts {
+            if attempt > 0 {
+                tokio::time::sleep(backoff_with_jitter(attempt)).await;
+            }
+
+            match self.send_once(&reqwest_method, &url, &headers, body.clone()).await {
+                Ok(response) => {
+                    if response.status.is_server_error() {
+                        last_response = Some(response);
+                        continue;
+                    }
+                    self.breaker.record_success();
+                    return Ok(response);
+                }
+                Err(e) => {
+                    last_err = Some(e);
+                }
+            }
+        }
+
+        self.breaker.record_failure();
+        // The most recent 5xx `ProxyResponse` (even if a later attempt failed to
+        // connect) still carries the real upstream status/body, so forward it as-is
+        // instead of collapsing it into a flat 502.
+        if let Some(response) = last_response {
+            return Ok(response);
+        }
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("Python API request failed")))
+    }
+
+    async fn send_once(
+        &self,
+        method: &ReqwestMethod,
+        url: &str,
+        headers: &HeaderMap,
+        body: Option<axum::body::Bytes>,
+    ) -> Result<ProxyResponse> {
+        let mut request = self.client.request(method.clone(), url);
+
         // Forward headers (excluding host and content-length)
         for (key, value) in headers.iter() {
             if key != "host" && key != "content-length" {
@@ -44,20 +218,30 @@ impl PythonClient {
                 }
             }
         }
-        
-        // Add body if present
+
         if let Some(body_bytes) = body {
             request = request.body(body_bytes.to_vec());
         }
-        
+
         let response = request.send().await?;
-        let status = response.status();
-        
-        if status.is_success() {
-            let json: Value = response.json().await?;
-            Ok(json)
+        let status = reqwest_status_to_axum(response.status());
+        let text = response.text().await.unwrap_or_default();
+        let body: Value = if text.is_empty() {
+            Value::Null
         } else {
-            Err(anyhow::anyhow!("Python API returned error: {}", status))
-        }
+            serde_json::from_str(&text).unwrap_or(Value::String(text))
+        };
+
+        Ok(ProxyResponse { status, body })
     }
 }
+
+fn reqwest_status_to_axum(status: ReqwestStatusCode) -> StatusCode {
+    StatusCode::from_u16(status.as_u16()).unwrap_or(StatusCode::BAD_GATEWAY)
+}
+
+fn backoff_with_jitter(attempt: u32) -> Duration {
+    let exp = BASE_BACKOFF * 2u32.pow(attempt.saturating_sub(1));
+    let jitter_millis = rand::thread_rng().gen_range(0..50);
+    exp + Duration::from_millis(jitter_millis)
+}
@@ -1,9 +1,10 @@
-use crate::database::{DatabaseService, User, Internship};
+use crate::database::{DatabaseService, Internship, Recommendation, User};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct RecommendationResponse {
     pub success: bool,
     pub recommendations: Vec<RecommendationWithDetails>,
@@ -11,7 +12,7 @@ pub struct RecommendationResponse {
     pub message: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct RecommendationWithDetails {
     pub internship: Internship,
     pub score: f64,
@@ -51,120 +52,167 @@ impl RecommendationService {
         })
     }
 
+    /// Ranks internships via TF-IDF cosine similarity between each internship's
+    /// document (required + preferred skills + description) and the user's skill
+    /// profile, then applies small experience/education boosts and a deadline
+    /// penalty on top of the raw similarity score.
     async fn calculate_recommendations(
         &self,
         user: &User,
         internships: &[Internship],
         limit: usize,
     ) -> Result<Vec<RecommendationWithDetails>> {
-        let mut scored_internships = Vec::new();
-
-        for internship in internships {
-            let score = self.calculate_score(user, internship).await?;
-            let explanation = self.generate_explanation(user, internship, score).await?;
-            
-            scored_internships.push(RecommendationWithDetails {
-                internship: internship.clone(),
+        let corpus: Vec<Vec<String>> = internships.iter().map(internship_tokens).collect();
+        let idf = compute_idf(&corpus);
+
+        let user_terms = tokenize(user.skills.as_deref().unwrap_or(""));
+        let user_vector = tfidf_vector(&user_terms, &idf);
+        let user_norm = vector_norm(&user_vector);
+
+        let today = chrono::Utc::now().date_naive();
+
+        let mut scored: Vec<Recommendation> = Vec::with_capacity(internships.len());
+        for (internship, doc_terms) in internships.iter().zip(corpus.iter()) {
+            let doc_vector = tfidf_vector(doc_terms, &idf);
+            let doc_norm = vector_norm(&doc_vector);
+
+            let mut score = if user_norm > 0.0 && doc_norm > 0.0 {
+                cosine_similarity(&user_vector, &doc_vector, user_norm, doc_norm)
+            } else {
+                0.0
+            };
+
+            if internship.experience_required <= user.experience_years {
+                score *= 1.1;
+            }
+            if let (Some(user_education), Some(min_education)) = (&user.education, &internship.min_education) {
+                if user_education.to_lowercase().contains(&min_education.to_lowercase()) {
+                    score *= 1.05;
+                }
+            }
+            if let Some(deadline) = internship
+                .application_deadline
+                .as_deref()
+                .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            {
+                if deadline < today {
+                    score *= 0.5;
+                }
+            }
+            score = score.min(1.0).max(0.0);
+
+            let explanation = explain(&user_vector, &doc_vector, score);
+
+            scored.push(Recommendation {
+                internship_id: internship.id,
                 score,
                 explanation,
             });
         }
 
-        // Sort by score (highest first) and take top N
-        scored_internships.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
-        scored_internships.truncate(limit);
+        scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+        scored.truncate(limit);
 
-        Ok(scored_internships)
+        let internships_by_id: HashMap<i32, &Internship> = internships.iter().map(|i| (i.id, i)).collect();
+        Ok(scored
+            .into_iter()
+            .filter_map(|rec| {
+                internships_by_id.get(&rec.internship_id).map(|internship| RecommendationWithDetails {
+                    internship: (*internship).clone(),
+                    score: rec.score,
+                    explanation: rec.explanation,
+                })
+            })
+            .collect())
     }
+}
 
-    async fn calculate_score(&self, user: &User, internship: &Internship) -> Result<f64> {
-        let mut score = 0.0;
-        
-        // Location matching (40% weight)
-        if let (Some(user_location), Some(internship_location)) = (&user.location, &internship.location) {
-            if user_location.to_lowercase().contains(&internship_location.to_lowercase()) ||
-               internship_location.to_lowercase().contains(&user_location.to_lowercase()) {
-                score += 0.4;
-            }
-        }
-        
-        // Skills matching (35% weight)
-        if let (Some(user_skills), Some(required_skills)) = (&user.skills, &internship.required_skills) {
-            let user_skills_list: Vec<&str> = user_skills.split(',').map(|s| s.trim()).collect();
-            let required_skills_list: Vec<&str> = required_skills.split(',').map(|s| s.trim()).collect();
-            
-            let matching_skills = user_skills_list.iter()
-                .filter(|skill| required_skills_list.iter().any(|req| 
-                    req.to_lowercase().contains(&skill.to_lowercase())
-                ))
-                .count();
-            
-            if !required_skills_list.is_empty() {
-                score += 0.35 * (matching_skills as f64 / required_skills_list.len() as f64);
-            }
-        }
-        
-        // Experience matching (15% weight)
-        let experience_match = if user.experience_years >= internship.experience_required {
-            1.0
-        } else {
-            user.experience_years as f64 / internship.experience_required as f64
-        };
-        score += 0.15 * experience_match;
-        
-        // Education matching (10% weight)
-        if let (Some(user_education), Some(min_education)) = (&user.education, &internship.min_education) {
-            if user_education.to_lowercase().contains(&min_education.to_lowercase()) {
-                score += 0.1;
-            }
-        }
-        
-        // Ensure score is between 0 and 1
-        Ok(score.min(1.0).max(0.0))
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(|t| t.to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn internship_tokens(internship: &Internship) -> Vec<String> {
+    let mut text = String::new();
+    if let Some(s) = &internship.required_skills {
+        text.push_str(s);
+        text.push(' ');
+    }
+    if let Some(s) = &internship.preferred_skills {
+        text.push_str(s);
+        text.push(' ');
     }
+    if let Some(s) = &internship.description {
+        text.push_str(s);
+    }
+    tokenize(&text)
+}
 
-    async fn generate_explanation(
-        &self,
-        user: &User,
-        internship: &Internship,
-        score: f64,
-    ) -> Result<String> {
-        let mut reasons = Vec::new();
-        
-        // Location match
-        if let (Some(user_location), Some(internship_location)) = (&user.location, &internship.location) {
-            if user_location.to_lowercase().contains(&internship_location.to_lowercase()) {
-                reasons.push(format!("Location match: {} and {}", user_location, internship_location));
-            }
-        }
-        
-        // Skills match
-        if let (Some(user_skills), Some(required_skills)) = (&user.skills, &internship.required_skills) {
-            let user_skills_list: Vec<&str> = user_skills.split(',').map(|s| s.trim()).collect();
-            let required_skills_list: Vec<&str> = required_skills.split(',').map(|s| s.trim()).collect();
-            
-            let matching_skills: Vec<&str> = user_skills_list.iter()
-                .filter(|skill| required_skills_list.iter().any(|req| 
-                    req.to_lowercase().contains(&skill.to_lowercase())
-                ))
-                .cloned()
-                .collect();
-            
-            if !matching_skills.is_empty() {
-                reasons.push(format!("Skills match: {}", matching_skills.join(", ")));
-            }
-        }
-        
-        // Experience match
-        if user.experience_years >= internship.experience_required {
-            reasons.push(format!("Experience requirement met: {} years", user.experience_years));
-        }
-        
-        if reasons.is_empty() {
-            reasons.push("Based on your profile and preferences".to_string());
+/// df(t) across the corpus, then idf(t) = ln(N / (1 + df(t))).
+fn compute_idf(corpus: &[Vec<String>]) -> HashMap<String, f64> {
+    let n = corpus.len() as f64;
+    let mut df: HashMap<String, usize> = HashMap::new();
+    for doc in corpus {
+        let unique: HashSet<&String> = doc.iter().collect();
+        for term in unique {
+            *df.entry(term.clone()).or_insert(0) += 1;
         }
-        
-        Ok(format!("Score: {:.1}% - {}", score * 100.0, reasons.join(", ")))
+    }
+
+    df.into_iter()
+        .map(|(term, count)| (term, (n / (1.0 + count as f64)).ln()))
+        .collect()
+}
+
+/// Sparse tf-idf vector for a token list: tf(t) = count(t)/len, weighted by idf(t).
+fn tfidf_vector(terms: &[String], idf: &HashMap<String, f64>) -> HashMap<String, f64> {
+    if terms.is_empty() {
+        return HashMap::new();
+    }
+
+    let mut counts: HashMap<&String, usize> = HashMap::new();
+    for term in terms {
+        *counts.entry(term).or_insert(0) += 1;
+    }
+
+    let len = terms.len() as f64;
+    counts
+        .into_iter()
+        .map(|(term, count)| {
+            let tf = count as f64 / len;
+            let weight = tf * idf.get(term).copied().unwrap_or(0.0);
+            (term.clone(), weight)
+        })
+        .collect()
+}
+
+fn vector_norm(vector: &HashMap<String, f64>) -> f64 {
+    vector.values().map(|v| v * v).sum::<f64>().sqrt()
+}
+
+fn cosine_similarity(a: &HashMap<String, f64>, b: &HashMap<String, f64>, norm_a: f64, norm_b: f64) -> f64 {
+    let (smaller, larger) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let dot: f64 = smaller.iter().filter_map(|(term, weight)| larger.get(term).map(|other| weight * other)).sum();
+    dot / (norm_a * norm_b)
+}
+
+/// Lists the top overlapping terms by their contribution to the dot product, as
+/// a human-readable explanation for why an internship scored the way it did.
+fn explain(user_vector: &HashMap<String, f64>, doc_vector: &HashMap<String, f64>, score: f64) -> String {
+    let mut contributions: Vec<(&String, f64)> = user_vector
+        .iter()
+        .filter_map(|(term, user_weight)| doc_vector.get(term).map(|doc_weight| (term, user_weight * doc_weight)))
+        .collect();
+    contributions.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    contributions.truncate(5);
+
+    if contributions.is_empty() {
+        format!("Score: {:.1}% - Based on your profile and preferences", score * 100.0)
+    } else {
+        let skills: Vec<&str> = contributions.into_iter().map(|(term, _)| term.as_str()).collect();
+        format!("Score: {:.1}% - Overlapping skills: {}", score * 100.0, skills.join(", "))
     }
 }
 
@@ -0,0 +1,54 @@
+use crate::database::DatabaseService;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, Serialize, utoipa::ToSchema)]
+pub struct TrackEventRequest {
+    pub device_id: String,
+    pub action: String,
+    #[serde(default)]
+    pub payload: serde_json::Value,
+}
+
+/// Records pre- and post-login user behavior against an anonymous `device_id`
+/// so `InsightsService` can attribute a user's full journey — not just the
+/// activity that happened after they signed in.
+pub struct TelemetryService {
+    db: Arc<DatabaseService>,
+}
+
+impl TelemetryService {
+    pub fn new(db: Arc<DatabaseService>) -> Self {
+        Self { db }
+    }
+
+    /// Stores one behavior event for `device_id`, tagging it with `user_id`
+    /// when the caller is already authenticated. Anonymous events (`user_id:
+    /// None`) are attributed later via [`TelemetryService::link_device`].
+    pub async fn track_event(
+        &self,
+        device_id: &str,
+        user_id: Option<i32>,
+        action: &str,
+        payload: serde_json::Value,
+    ) -> Result<()> {
+        let mut event = match payload {
+            serde_json::Value::Object(map) => serde_json::Value::Object(map),
+            _ => serde_json::json!({}),
+        };
+        event["action"] = serde_json::Value::String(action.to_string());
+
+        let behavior_data = serde_json::to_string(&event)?;
+        self.db
+            .insert_behavior_event(device_id, user_id, &behavior_data)
+            .await?;
+        Ok(())
+    }
+
+    /// Back-fills `user_id` onto every prior anonymous event recorded for
+    /// `device_id`, called the moment that device authenticates.
+    pub async fn link_device(&self, device_id: &str, user_id: i32) -> Result<()> {
+        self.db.link_device(device_id, user_id).await
+    }
+}
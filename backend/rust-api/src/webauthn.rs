@@ -0,0 +1,201 @@
+use crate::database::DatabaseService;
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use webauthn_rs::prelude::*;
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RegisterStartRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RegisterStartResponse {
+    pub success: bool,
+    pub challenge: CreationChallengeResponse,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterFinishRequest {
+    pub email: String,
+    pub credential: RegisterPublicKeyCredential,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LoginStartRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoginStartResponse {
+    pub success: bool,
+    pub challenge: RequestChallengeResponse,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoginFinishRequest {
+    pub email: String,
+    pub credential: PublicKeyCredential,
+}
+
+/// `CredentialID` (`webauthn_rs`'s `HumanBinaryData`) implements `Debug`/`Serialize`
+/// but deliberately not `Display`, so credential ids are stored/looked-up as an
+/// explicit base64url encoding of the raw bytes instead of `.to_string()`.
+fn encode_cred_id(id: &CredentialID) -> String {
+    URL_SAFE_NO_PAD.encode(id.as_ref())
+}
+
+/// Passwordless registration/login via WebAuthn (hardware keys and platform
+/// passkeys), alongside the password and SSO paths in `AuthService`. Ceremony
+/// state (the random challenge issued to the browser) is held in memory keyed
+/// by email between the `start` and `finish` calls, mirroring `SsoService`'s
+/// pending-state map.
+pub struct WebauthnService {
+    webauthn: Webauthn,
+    db: Arc<DatabaseService>,
+    pending_registrations: Mutex<HashMap<String, PasskeyRegistration>>,
+    pending_authentications: Mutex<HashMap<String, PasskeyAuthentication>>,
+}
+
+impl WebauthnService {
+    pub fn new(db: Arc<DatabaseService>) -> Self {
+        let rp_id = std::env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| "localhost".to_string());
+        let rp_origin_url = std::env::var("WEBAUTHN_RP_ORIGIN").unwrap_or_else(|_| "http://localhost:3001".to_string());
+        let rp_origin = Url::parse(&rp_origin_url).expect("Invalid WEBAUTHN_RP_ORIGIN");
+
+        let webauthn = WebauthnBuilder::new(&rp_id, &rp_origin)
+            .expect("Invalid WebAuthn relying-party configuration")
+            .rp_name("InternGenie")
+            .build()
+            .expect("Failed to build WebAuthn instance");
+
+        Self {
+            webauthn,
+            db,
+            pending_registrations: Mutex::new(HashMap::new()),
+            pending_authentications: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn register_start(&self, email: &str) -> Result<RegisterStartResponse> {
+        let user = self.db.get_user_by_email(email).await?;
+        let user_unique_id = Uuid::new_v5(&Uuid::NAMESPACE_OID, user.id.to_string().as_bytes());
+
+        let existing_credentials = self.existing_credential_ids(user.id).await?;
+
+        let (challenge, registration_state) = self.webauthn.start_passkey_registration(
+            user_unique_id,
+            &user.email,
+            &user.name,
+            Some(existing_credentials),
+        )?;
+
+        self.pending_registrations
+            .lock()
+            .await
+            .insert(email.to_string(), registration_state);
+
+        Ok(RegisterStartResponse {
+            success: true,
+            challenge,
+        })
+    }
+
+    pub async fn register_finish(&self, request: &RegisterFinishRequest) -> Result<()> {
+        let registration_state = self
+            .pending_registrations
+            .lock()
+            .await
+            .remove(&request.email)
+            .ok_or_else(|| anyhow!("No pending registration for this email"))?;
+
+        let passkey = self
+            .webauthn
+            .finish_passkey_registration(&request.credential, &registration_state)?;
+
+        let user = self.db.get_user_by_email(&request.email).await?;
+        let credential_id = encode_cred_id(passkey.cred_id());
+        let credential_json = serde_json::to_string(&passkey)?;
+        self.db.add_webauthn_credential(user.id, &credential_id, &credential_json).await?;
+
+        Ok(())
+    }
+
+    pub async fn login_start(&self, email: &str) -> Result<LoginStartResponse> {
+        let user = self.db.get_user_by_email(email).await?;
+        let passkeys = self.load_passkeys(user.id).await?;
+
+        if passkeys.is_empty() {
+            return Err(anyhow!("No registered passkeys for this user"));
+        }
+
+        let (challenge, auth_state) = self.webauthn.start_passkey_authentication(&passkeys)?;
+
+        self.pending_authentications
+            .lock()
+            .await
+            .insert(email.to_string(), auth_state);
+
+        Ok(LoginStartResponse {
+            success: true,
+            challenge,
+        })
+    }
+
+    /// Verifies the assertion signature against the stored public key and
+    /// enforces the signature counter strictly increases (clone detection) via
+    /// `webauthn-rs`'s built-in counter check, then persists the bumped counter.
+    pub async fn login_finish(&self, request: &LoginFinishRequest) -> Result<i32> {
+        let auth_state = self
+            .pending_authentications
+            .lock()
+            .await
+            .remove(&request.email)
+            .ok_or_else(|| anyhow!("No pending authentication for this email"))?;
+
+        let user = self.db.get_user_by_email(&request.email).await?;
+        let result = self
+            .webauthn
+            .finish_passkey_authentication(&request.credential, &auth_state)?;
+
+        if result.needs_update() {
+            let credential_id = encode_cred_id(result.cred_id());
+            if let Some(mut passkey) = self.find_passkey(user.id, &credential_id).await? {
+                passkey.update_credential(&result);
+                let credential_json = serde_json::to_string(&passkey)?;
+                self.db.update_webauthn_credential(&credential_id, &credential_json).await?;
+            }
+        }
+
+        Ok(user.id)
+    }
+
+    async fn existing_credential_ids(&self, user_id: i32) -> Result<Vec<CredentialID>> {
+        Ok(self
+            .load_passkeys(user_id)
+            .await?
+            .iter()
+            .map(|passkey| passkey.cred_id().clone())
+            .collect())
+    }
+
+    async fn load_passkeys(&self, user_id: i32) -> Result<Vec<Passkey>> {
+        let raw = self.db.get_webauthn_credentials(user_id).await?;
+        let mut passkeys = Vec::with_capacity(raw.len());
+        for json in raw {
+            passkeys.push(serde_json::from_str(&json)?);
+        }
+        Ok(passkeys)
+    }
+
+    async fn find_passkey(&self, user_id: i32, credential_id: &str) -> Result<Option<Passkey>> {
+        Ok(self
+            .load_passkeys(user_id)
+            .await?
+            .into_iter()
+            .find(|passkey| encode_cred_id(passkey.cred_id()) == credential_id))
+    }
+}